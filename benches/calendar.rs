@@ -0,0 +1,79 @@
+//! 月历网格与每帧日期格状态的耗时，不包含 GPUI 的布局与绘制。
+//!
+//! `baseline` 按引入 `GridCache` 之前的做法每帧重新计算网格、每格各取一次系统时间，
+//! 与 `cached` 对比可以看出缓存网格、每帧只取一次「今天」省下的开销。
+//!
+//! ```sh
+//! cargo bench --bench calendar --no-default-features
+//! ```
+
+use std::hint::black_box;
+
+use chrono::NaiveDate;
+use criterion::{Criterion, criterion_group, criterion_main};
+use gpui_demo::{Calendar, GridCache};
+
+fn calendar() -> Calendar {
+    Calendar {
+        current_month: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        selected_date: NaiveDate::from_ymd_opt(2025, 3, 14),
+        ..Calendar::new()
+    }
+}
+
+/// 渲染一个日期格需要的状态，与 `DatePicker::render_day_cell` 一致
+fn cell_state(calendar: &Calendar, date: &NaiveDate, is_today: bool) -> (bool, bool, bool, bool) {
+    (
+        is_today,
+        calendar.is_selected(date),
+        calendar.is_in_range(date),
+        calendar.is_selectable(date),
+    )
+}
+
+fn month_grid(c: &mut Criterion) {
+    let calendar = calendar();
+    let mut group = c.benchmark_group("month_grid");
+    group.bench_function("fresh", |b| b.iter(|| black_box(&calendar).get_month_days()));
+    group.bench_function("cached", |b| {
+        let mut cache = GridCache::default();
+        b.iter(|| cache.get(black_box(&calendar)))
+    });
+    group.finish();
+}
+
+/// 一帧内一个月历网格的全部非布局开销
+fn grid_frame(c: &mut Criterion) {
+    let calendar = calendar();
+    let mut group = c.benchmark_group("grid_frame");
+    // 每帧重新计算网格，每格的 `is_today` 都读一次系统时间
+    group.bench_function("baseline", |b| {
+        b.iter(|| {
+            let calendar = black_box(&calendar);
+            calendar
+                .get_month_days()
+                .iter()
+                .flatten()
+                .map(|date| cell_state(calendar, date, calendar.is_today(date)))
+                .collect::<Vec<_>>()
+        })
+    });
+    // 网格命中缓存，「今天」每帧只取一次
+    group.bench_function("cached", |b| {
+        let mut cache = GridCache::default();
+        b.iter(|| {
+            let calendar = black_box(&calendar);
+            let today = calendar.today();
+            cache
+                .get(calendar)
+                .iter()
+                .flatten()
+                .map(|date| cell_state(calendar, date, *date == today))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, month_grid, grid_frame);
+criterion_main!(benches);
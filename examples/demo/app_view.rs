@@ -0,0 +1,359 @@
+use chrono::{Datelike, Days, NaiveDate, NaiveTime, Weekday};
+use gpui::*;
+use gpui_demo::{
+    ActiveTheme, Clock, DatePicker, DayCellState, DayDecoration, DayDecorator, DayItem, DatePickerEvent, DateRules, DisplayMode, GridOptions, Heatmap, HeatmapData,
+    HeatmapEvent, Locale, MonthPicker, Period, PeriodPicker, PeriodPickerEvent, QuarterPicker, RelativeStyle,
+    SelectionMode, Theme, ThemeMode, ThemeState, Tz, WorkingDays, YearColumns, YearPicker, YearView,
+    set_theme_mode,
+};
+
+pub struct AppView {
+    pub pickers: Vec<Entity<DatePicker>>,
+    pub month_picker: Entity<MonthPicker>,
+    pub quarter_picker: Entity<QuarterPicker>,
+    pub year_picker: Entity<YearPicker>,
+    pub year_view: Entity<YearView>,
+    pub heatmap: Entity<Heatmap>,
+    last_event: Option<String>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl AppView {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self::with_clock(Clock::System, window, cx)
+    }
+
+    /// 所有示例共用的时钟；纽约时区的示例除外。测试传入 `Clock::Fixed`，让「今天」固定下来
+    pub fn with_clock(clock: Clock, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let notes = [
+            "示例1：应在下方展开，带今天/清除与相对说明",
+            "示例2：应在下方展开，可选相邻月份",
+            "示例3：可能上方展开，需确认",
+            "示例4：可能上方展开，隐藏相邻月份",
+        ];
+        // 简化：根据所在位置直接决定方向
+        // 上两行向下展开，下两行向上展开
+        let mut pickers: Vec<_> = notes
+            .iter()
+            .enumerate()
+            .map(|(idx, note)| {
+                let grid_options = match idx {
+                    1 => GridOptions {
+                        select_adjacent_days: true,
+                        ..Default::default()
+                    },
+                    3 => GridOptions {
+                        show_adjacent_days: false,
+                        trim_trailing_rows: true,
+                        ..Default::default()
+                    },
+                    _ => GridOptions::default(),
+                };
+                cx.new(|cx| {
+                    let picker = DatePicker::new(cx)
+                        .clock(clock)
+                        .id(format!("picker-{idx}"))
+                        .label(*note)
+                        .show_above(idx >= 2)
+                        .grid_options(grid_options)
+                        .show_footer(idx == 0)
+                        .require_confirm(idx == 2);
+                    if idx == 0 {
+                        picker.relative_caption(RelativeStyle::Relative)
+                    } else {
+                        picker
+                    }
+                })
+            })
+            .collect();
+        pickers.push(cx.new(|cx| {
+            DatePicker::new(cx)
+                .clock(clock)
+                .id("picker-range")
+                .label("示例5：范围选择，双月与快捷选项，显示工作日数，每天标出房价")
+                .selection_mode(SelectionMode::Range)
+                .show_working_days(true)
+                .decorator(WeekendDecorator)
+                .decorator(PriceDecorator)
+        }));
+        pickers.push(cx.new(|cx| {
+            DatePicker::new(cx)
+                .clock(clock)
+                .id("picker-compare")
+                .label("示例6：对比范围，默认对比上一周期")
+                .selection_mode(SelectionMode::Comparison)
+        }));
+        pickers.push(cx.new(|cx| {
+            DatePicker::new(cx)
+                .clock(clock)
+                .id("picker-inline")
+                .label("示例7：内嵌日历，支持方向键，右键日期打开菜单")
+                .display_mode(DisplayMode::Inline)
+                .context_menu_item("复制为中文日期", |date, _, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(date.format("%Y年%-m月%-d日").to_string()));
+                })
+        }));
+        pickers.push(cx.new(|cx| {
+            DatePicker::new(cx)
+                .clock(clock)
+                .id("picker-week")
+                .label("示例8：按 ISO 周选择")
+                .selection_mode(SelectionMode::Week)
+        }));
+        pickers.push(cx.new(|cx| {
+            DatePicker::new(cx)
+                .id("picker-zone")
+                .label("示例9：按纽约时间计算今天，英文截止说明")
+                .time_zone(Tz::America__New_York)
+                .show_footer(true)
+                .locale(Locale::En)
+                .relative_caption(RelativeStyle::Deadline)
+        }));
+        pickers.push(cx.new(|cx| {
+            DatePicker::new(cx)
+                .clock(clock)
+                .id("picker-scroll")
+                .label("示例10：连续滚动的月份，吸顶标题，可回到今天")
+                .display_mode(DisplayMode::Scroll)
+        }));
+
+        let mut subscriptions: Vec<_> = pickers
+            .iter()
+            .enumerate()
+            .map(|(idx, picker)| {
+                cx.subscribe(picker, move |app, _, event: &DatePickerEvent, cx| {
+                    app.last_event = Some(match event {
+                        DatePickerEvent::Changed(Some(value)) => format!("示例{}：选择了 {}", idx + 1, value),
+                        DatePickerEvent::Changed(None) => format!("示例{}：清除了日期", idx + 1),
+                        DatePickerEvent::Opened => format!("示例{}：打开", idx + 1),
+                        DatePickerEvent::Closed => format!("示例{}：关闭", idx + 1),
+                        DatePickerEvent::NewEvent(date) => format!("示例{}：在 {} 新建日程", idx + 1, date),
+                    });
+                    cx.notify();
+                })
+            })
+            .collect();
+
+        let today = clock.today();
+        let month_picker = cx.new(|_| {
+            MonthPicker::new()
+                .clock(clock)
+                .id("picker-month")
+                .label("月份：不能选择未来月份")
+                .rules(DateRules::default().max_date(today))
+        });
+        let quarter_picker = cx.new(|_| QuarterPicker::new().clock(clock).id("picker-quarter").label("季度"));
+        let year_picker = cx.new(|_| {
+            YearPicker::new()
+                .clock(clock)
+                .id("picker-year")
+                .label("年份：2000 年起")
+                .rules(DateRules::default().min_date(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()))
+        });
+        subscriptions.push(Self::subscribe_period(&month_picker, "月份", cx));
+        subscriptions.push(Self::subscribe_period(&quarter_picker, "季度", cx));
+        subscriptions.push(Self::subscribe_period(&year_picker, "年份", cx));
+
+        // 年视图：元旦、国庆标为节假日，今天之后的两天标为已排的假
+        let holidays = [(1, 1), (10, 1)]
+            .into_iter()
+            .filter_map(|(month, day)| NaiveDate::from_ymd_opt(today.year(), month, day));
+        let leave_days: Vec<NaiveDate> =
+            [3, 4].into_iter().filter_map(|days| today.checked_add_days(Days::new(days))).collect();
+        // 弹层中悬停日期时列出假期与每周一的周会
+        let leave_tooltip = leave_days.clone();
+        let year_view_picker = cx.new(|cx| {
+            DatePicker::new(cx)
+                .clock(clock)
+                .id("picker-year-view")
+                .working_days(WorkingDays::default().holidays(holidays))
+                .day_tooltip(move |date| {
+                    let mut items = Vec::new();
+                    if leave_tooltip.contains(&date) {
+                        items.push(DayItem::new("年假", rgb(0x16a34a)));
+                    }
+                    if date.weekday() == Weekday::Mon {
+                        let start = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+                        let end = NaiveTime::from_hms_opt(11, 0, 0).unwrap();
+                        items.push(DayItem::new("周会", rgb(0x2563eb)).time(start, end));
+                    }
+                    items
+                })
+        });
+        // 热力图：按日期生成一年的示例数据，周末较少
+        let activity: HeatmapData = (0..365u64)
+            .filter_map(|days| today.checked_sub_days(Days::new(days)))
+            .map(|date| {
+                let base = (date.ordinal() * 7 % 13) as f64;
+                (date, if date.weekday().num_days_from_monday() >= 5 { base / 4.0 } else { base })
+            })
+            .collect();
+        let heatmap = cx.new(|_| {
+            Heatmap::new()
+                .clock(clock)
+                .id("heatmap")
+                .data(activity)
+                .thresholds([1.0, 4.0, 8.0, 11.0])
+        });
+        subscriptions.push(cx.subscribe(&heatmap, |app, _, event: &HeatmapEvent, cx| {
+            let HeatmapEvent::Changed(value) = event;
+            app.last_event = Some(match value {
+                Some(value) => format!("热力图：选择了 {value}"),
+                None => "热力图：清除了选择".to_string(),
+            });
+            cx.notify();
+        }));
+
+        let year_view = cx.new(|cx| {
+            YearView::new(year_view_picker, cx)
+                .id("year-view")
+                .columns(YearColumns::Six)
+                .events(leave_days)
+        });
+
+        Self {
+            pickers,
+            month_picker,
+            quarter_picker,
+            year_picker,
+            year_view,
+            heatmap,
+            last_event: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn subscribe_period<P: Period>(
+        picker: &Entity<PeriodPicker<P>>,
+        name: &'static str,
+        cx: &mut Context<Self>,
+    ) -> Subscription {
+        cx.subscribe(picker, move |app, _, event: &PeriodPickerEvent<P>, cx| {
+            app.last_event = Some(match event {
+                PeriodPickerEvent::Changed(Some(value)) => format!("{name}：选择了 {value}"),
+                PeriodPickerEvent::Changed(None) => format!("{name}：清除了选择"),
+                PeriodPickerEvent::Opened => format!("{name}：打开"),
+                PeriodPickerEvent::Closed => format!("{name}：关闭"),
+            });
+            cx.notify();
+        })
+    }
+
+    fn render_theme_bar(active: ThemeMode, theme: &Theme) -> impl IntoElement {
+        let theme = *theme;
+        let modes = [
+            (ThemeMode::Light, "浅色"),
+            (ThemeMode::Dark, "深色"),
+            (ThemeMode::HighContrast, "高对比"),
+            (ThemeMode::System, "跟随系统"),
+        ];
+
+        div()
+            .flex()
+            .w_full()
+            .gap_2()
+            .children(modes.into_iter().map(|(mode, label)| {
+                let is_active = mode == active;
+                div()
+                    .px_3()
+                    .py_1()
+                    .border(px(1.0))
+                    .border_color(if is_active { theme.accent } else { theme.border })
+                    .rounded_md()
+                    .bg(if is_active { theme.accent } else { theme.surface })
+                    .text_sm()
+                    .text_color(if is_active { theme.accent_text } else { theme.text })
+                    .cursor_pointer()
+                    .hover(move |style| style.border_color(theme.accent))
+                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                        set_theme_mode(mode, window, cx);
+                    })
+                    .child(label)
+            }))
+    }
+}
+
+impl Render for AppView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        let offsets = [0.0, 0.0, 360.0, 360.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let theme = *cx.theme();
+        let theme_mode = cx.global::<ThemeState>().mode;
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_start()
+            .justify_start()
+            .gap_6()
+            .p_6()
+            .size_full()
+            .bg(theme.background)
+            .text_color(theme.text)
+            .child(AppView::render_theme_bar(theme_mode, &theme))
+            .child(
+                div()
+                    .w_full()
+                    .text_sm()
+                    .text_color(theme.text_muted)
+                    .child(self.last_event.clone().unwrap_or_else(|| "最近事件：无".to_string()))
+            )
+            .child(
+                div()
+                    .flex()
+                    .w_full()
+                    .gap_6()
+                    .child(self.month_picker.clone())
+                    .child(self.quarter_picker.clone())
+                    .child(self.year_picker.clone())
+            )
+            .children(
+                self.pickers
+                    .iter()
+                    .zip(offsets)
+                    .map(|(picker, offset_top)| div().mt(px(offset_top)).child(picker.clone())),
+            )
+            .child(div().w_full().child(self.heatmap.clone()))
+            .child(div().w_full().child(self.year_view.clone()))
+    }
+}
+
+/// 示例房价：按日期生成，周末更贵
+fn room_price(date: NaiveDate) -> u32 {
+    let base = 200 + date.ordinal() * 37 % 9 * 20;
+    if date.weekday().num_days_from_monday() >= 5 { base + 100 } else { base }
+}
+
+/// 在日期数字下方标出房价
+struct PriceDecorator;
+
+impl DayDecorator for PriceDecorator {
+    fn decorate(&self, date: NaiveDate, state: DayCellState, _theme: &Theme) -> DayDecoration {
+        if state.is_disabled || !state.is_current_month {
+            return DayDecoration::default();
+        }
+        DayDecoration::default().child(
+            div()
+                .debug_selector(move || format!("price-{date}"))
+                .text_size(px(9.0))
+                .child(format!("¥{}", room_price(date))),
+        )
+    }
+}
+
+/// 周末用节假日的颜色，优先级高于房价
+struct WeekendDecorator;
+
+impl DayDecorator for WeekendDecorator {
+    fn priority(&self) -> i32 {
+        1
+    }
+
+    fn decorate(&self, date: NaiveDate, state: DayCellState, theme: &Theme) -> DayDecoration {
+        let is_weekend = date.weekday().num_days_from_monday() >= 5;
+        if is_weekend && state.is_current_month && !state.is_selected && !state.is_disabled {
+            DayDecoration::default().text_color(theme.holiday_text)
+        } else {
+            DayDecoration::default()
+        }
+    }
+}
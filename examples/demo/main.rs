@@ -0,0 +1,24 @@
+#![windows_subsystem = "windows"]
+
+mod app_view;
+
+use app_view::AppView;
+use gpui::*;
+
+fn main() {
+    Application::new()
+        .run(|cx: &mut App| {
+            gpui_demo::init(cx);
+            let bounds = Bounds::centered(None, size(px(1000.0), px(760.0)), cx);
+            cx.open_window(
+                WindowOptions {
+                    window_bounds: Some(WindowBounds::Windowed(bounds)),
+                    ..Default::default()
+                },
+                |window: &mut Window, cx: &mut App| {
+                    cx.new(|cx| AppView::new(window, cx))
+                },
+            )
+            .unwrap();
+        });
+}
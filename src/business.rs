@@ -0,0 +1,99 @@
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::calendar::add_days;
+use crate::range::DateRange;
+
+/// 工作日规则：哪些星期是周末，以及额外的节假日。
+///
+/// 默认周六、周日为周末；中东地区等可以改为周五、周六。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkingDays {
+    pub weekend: Vec<Weekday>,
+    pub holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for WorkingDays {
+    fn default() -> Self {
+        Self {
+            weekend: vec![Weekday::Sat, Weekday::Sun],
+            holidays: BTreeSet::new(),
+        }
+    }
+}
+
+impl WorkingDays {
+    /// 替换周末的星期
+    pub fn weekend(mut self, weekend: impl IntoIterator<Item = Weekday>) -> Self {
+        self.weekend = weekend.into_iter().collect();
+        self
+    }
+
+    pub fn holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    pub fn is_working_day(&self, date: &NaiveDate) -> bool {
+        !self.weekend.contains(&date.weekday()) && !self.holidays.contains(date)
+    }
+
+    /// 去重后的周末，按周一起的序号表示
+    fn weekend_indices(&self) -> BTreeSet<u32> {
+        self.weekend.iter().map(|day| day.num_days_from_monday()).collect()
+    }
+
+    /// 每周都是周末时不存在工作日，加减和查找都会返回 `None`
+    fn has_working_weekdays(&self) -> bool {
+        self.weekend_indices().len() < 7
+    }
+
+    /// 前后移动 `days` 个工作日，不计起始日；`days` 为 0 时返回原日期，超出范围时返回 `None`
+    pub fn add(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        if days != 0 && !self.has_working_weekdays() {
+            return None;
+        }
+        let step = days.signum();
+        let mut date = date;
+        let mut remaining = days.unsigned_abs();
+        while remaining > 0 {
+            date = add_days(date, step)?;
+            if self.is_working_day(&date) {
+                remaining -= 1;
+            }
+        }
+        Some(date)
+    }
+
+    /// 日期之后的第一个工作日
+    pub fn next_working_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.add(date, 1)
+    }
+
+    /// 范围内（包含两端）的工作日数
+    pub fn count(&self, range: DateRange) -> i64 {
+        let total = range.num_days();
+        let weekend_days = self.weekend_days_in(range.start, total);
+        let holidays = self
+            .holidays
+            .range(range.start..=range.end)
+            .filter(|date| !self.weekend.contains(&date.weekday()))
+            .count() as i64;
+        total - weekend_days - holidays
+    }
+
+    /// 从 `start` 起 `total` 天中落在周末的天数
+    fn weekend_days_in(&self, start: NaiveDate, total: i64) -> i64 {
+        let full_weeks = total / 7;
+        let first = start.weekday().num_days_from_monday() as i64;
+        self.weekend_indices()
+            .into_iter()
+            .map(|day| {
+                // 不满一周的剩余天数中是否包含这个星期
+                let offset = (day as i64 - first).rem_euclid(7);
+                full_weeks + i64::from(offset < total % 7)
+            })
+            .sum()
+    }
+}
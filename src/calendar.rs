@@ -0,0 +1,656 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use chrono::{DateTime, Datelike, Days, FixedOffset, Months, NaiveDate, Weekday};
+
+use crate::business::WorkingDays;
+use crate::clock::Clock;
+use crate::format::{DateFormat, ParseDateError};
+use crate::period::Week;
+use crate::range::{CompareMode, DateRange, RangePreset};
+use crate::rules::DateRules;
+use crate::selection::{CompareTarget, DateValue, SelectionMode};
+
+/// 月历网格的显示与选择选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GridOptions {
+    /// 是否显示前后相邻月份的日期，隐藏时对应格子为 `None`
+    pub show_adjacent_days: bool,
+    /// 相邻月份的日期能否被选中，选中后切换到该日期所在月份
+    pub select_adjacent_days: bool,
+    /// 去掉末尾不含本月日期的整行，而不是固定补齐到 42 格
+    pub trim_trailing_rows: bool,
+    /// 每行的第一天，选周模式下固定为周一
+    pub week_start: Weekday,
+}
+
+impl Default for GridOptions {
+    fn default() -> Self {
+        Self {
+            show_adjacent_days: true,
+            select_adjacent_days: false,
+            trim_trailing_rows: false,
+            week_start: Weekday::Sun,
+        }
+    }
+}
+
+/// 日历的核心状态：当前显示的月份与选中的日期或范围。
+///
+/// 不依赖 GPUI，可单独用于计算月历网格。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    /// 当前显示的月份，导航后总是该月 1 号
+    pub current_month: NaiveDate,
+    pub selected_date: Option<NaiveDate>,
+    pub selected_range: Option<DateRange>,
+    /// 范围选择进行中时已点击的第一个端点
+    pub range_anchor: Option<NaiveDate>,
+    /// 范围选择进行中时鼠标所在的日期，与起点一起构成预览范围
+    pub range_hover: Option<NaiveDate>,
+    /// 对比模式下手动选择的对比范围，仅在 `CompareMode::Custom` 时使用
+    pub custom_compare_range: Option<DateRange>,
+    pub compare_mode: CompareMode,
+    pub compare_target: CompareTarget,
+    pub selection_mode: SelectionMode,
+    pub grid_options: GridOptions,
+    /// 最早/最晚日期与禁用日期，不满足的日期不能被点击选中
+    pub rules: DateRules,
+    /// 周末与节假日，用于工作日的加减与计数
+    pub working_days: WorkingDays,
+    pub clock: Clock,
+    /// 键盘导航的光标日期，鼠标选择后清空
+    pub focused_date: Option<NaiveDate>,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calendar {
+    /// 以今天为当前月份和选中日期创建日历
+    pub fn new() -> Self {
+        Self::with_clock(Clock::System)
+    }
+
+    /// 以给定时钟的今天为当前月份和选中日期创建日历
+    pub fn with_clock(clock: Clock) -> Self {
+        let today = clock.today();
+        Self {
+            current_month: first_of_month(today),
+            selected_date: Some(today),
+            selected_range: None,
+            range_anchor: None,
+            range_hover: None,
+            custom_compare_range: None,
+            compare_mode: CompareMode::default(),
+            compare_target: CompareTarget::Primary,
+            selection_mode: SelectionMode::Single,
+            grid_options: GridOptions::default(),
+            rules: DateRules::default(),
+            working_days: WorkingDays::default(),
+            clock,
+            focused_date: None,
+        }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.clock.today()
+    }
+
+    /// 上一个月；已是最早的月份时保持不变
+    pub fn prev_month(&mut self) {
+        self.shift_months(-1);
+    }
+
+    /// 下一个月；已是最晚的月份时保持不变
+    pub fn next_month(&mut self) {
+        self.shift_months(1);
+    }
+
+    /// 上一年；超出 `NaiveDate` 范围时停在最早的月份
+    pub fn prev_year(&mut self) {
+        self.shift_months(-12);
+    }
+
+    /// 下一年；超出 `NaiveDate` 范围时停在最晚的月份
+    pub fn next_year(&mut self) {
+        self.shift_months(12);
+    }
+
+    /// 按年平移，月份选择器等按页翻动时使用
+    pub fn shift_years(&mut self, years: i32) {
+        self.shift_months(years.saturating_mul(12));
+    }
+
+    /// 当前年份每个月的 1 号，年视图按此排列 12 个小月历
+    pub fn year_months(&self) -> [NaiveDate; 12] {
+        let january = self.current_month.with_month(1).unwrap_or(self.current_month);
+        std::array::from_fn(|idx| shift_month(january, idx as i32))
+    }
+
+    /// 以 `month` 为当前月份、隐藏相邻月份的副本，用于同时显示多个月时逐月计算网格，避免同一天出现两次
+    pub fn month_view(&self, month: NaiveDate) -> Calendar {
+        let mut view = self.clone();
+        view.current_month = first_of_month(month);
+        view.grid_options.show_adjacent_days = false;
+        view
+    }
+
+    /// 同时显示的月份数，范围与对比模式并排显示两个月
+    pub fn visible_months(&self) -> u32 {
+        match self.selection_mode {
+            SelectionMode::Single | SelectionMode::Week => 1,
+            SelectionMode::Range | SelectionMode::Comparison => 2,
+        }
+    }
+
+    /// 网格每行的第一天；ISO 周从周一开始，选周模式下每行正好是一周
+    pub fn week_start(&self) -> Weekday {
+        match self.selection_mode {
+            SelectionMode::Week => Weekday::Mon,
+            _ => self.grid_options.week_start,
+        }
+    }
+
+    /// 日期位于当前显示的某个月份中
+    pub fn is_visible(&self, date: &NaiveDate) -> bool {
+        let first_day = first_of_month(self.current_month);
+        let last_day = first_day
+            .checked_add_months(Months::new(self.visible_months()))
+            .and_then(|date| date.pred_opt())
+            .unwrap_or(NaiveDate::MAX);
+        first_day <= *date && *date <= last_day
+    }
+
+    /// 键盘光标所在日期；尚未移动过时取可见的选中日期、今天或当前月份 1 号
+    pub fn focus_origin(&self) -> NaiveDate {
+        let today = self.today();
+        self.focused_date
+            .or(self.range_anchor)
+            .or(self.selected_date.filter(|date| self.is_visible(date)))
+            .or(Some(today).filter(|date| self.is_visible(date)))
+            .unwrap_or(first_of_month(self.current_month))
+    }
+
+    /// 按天移动键盘光标，移出可见月份时跟随翻月；越界时保持不变
+    pub fn move_focus(&mut self, days: i64) {
+        if let Some(date) = add_days(self.focus_origin(), days) {
+            self.focused_date = Some(date);
+            if !self.is_visible(&date) {
+                self.current_month = first_of_month(date);
+            }
+        }
+    }
+
+    /// 按月移动键盘光标，日期超出目标月份天数时取该月最后一天
+    pub fn move_focus_months(&mut self, months: i32) {
+        let origin = self.focus_origin();
+        let month = shift_month(origin, months);
+        let date = month
+            .with_day(origin.day().min(days_in_month(month)))
+            .unwrap_or(month);
+        self.focused_date = Some(date);
+        if !self.is_visible(&date) {
+            self.current_month = month;
+        }
+    }
+
+    /// 按月平移当前月份，正数向后；越界时停在最早/最晚的月份
+    pub fn shift_months(&mut self, months: i32) {
+        self.current_month = shift_month(self.current_month, months);
+    }
+
+    /// 当前月份的 6×7 网格，每行从 [`week_start`](Self::week_start) 开始，前后用相邻月份的日期补齐 42 格。
+    ///
+    /// 隐藏相邻月份时对应格子为 `None`；去掉末尾空行时长度为 7 的倍数且可能少于 42。
+    /// 超出 `NaiveDate` 范围的格子同样为 `None`。
+    pub fn get_month_days(&self) -> Vec<Option<NaiveDate>> {
+        let options = self.grid_options;
+        let first_day = first_of_month(self.current_month);
+        let leading = first_day.weekday().days_since(self.week_start()) as i64;
+        let cells = leading as usize + days_in_month(first_day) as usize;
+
+        let total = if options.trim_trailing_rows {
+            cells.div_ceil(7) * 7
+        } else {
+            42
+        };
+
+        (0..total as i64)
+            .map(|idx| {
+                let date = add_days(first_day, idx - leading)?;
+                (options.show_adjacent_days || self.is_current_month(&date)).then_some(date)
+            })
+            .collect()
+    }
+
+    /// 选中日期；日期不在当前月份时切换到它所在的月份
+    pub fn select_date(&mut self, date: NaiveDate) {
+        self.selected_date = Some(date);
+        if !self.is_current_month(&date) {
+            self.current_month = first_of_month(date);
+        }
+    }
+
+    /// 按选择模式处理一次日期点击，返回本次选择是否已完成。
+    ///
+    /// 单选模式总是完成；范围模式第一次点击只记录起点，第二次点击才完成；
+    /// 对比模式在手动选择对比范围时，主范围完成后继续选择对比范围。
+    pub fn pick(&mut self, date: NaiveDate) -> bool {
+        match self.selection_mode {
+            SelectionMode::Single => {
+                self.select_date(date);
+                true
+            }
+            // 点击的可能是相邻月份的日期，整行属于同一周，不翻月
+            SelectionMode::Week => {
+                self.selected_date = Some(date);
+                true
+            }
+            SelectionMode::Range | SelectionMode::Comparison => match self.range_anchor.take() {
+                Some(anchor) => {
+                    self.range_hover = None;
+                    self.finish_range(DateRange::new(anchor, date))
+                }
+                None => {
+                    self.range_anchor = Some(date);
+                    self.range_hover = Some(date);
+                    false
+                }
+            },
+        }
+    }
+
+    /// 应用快捷范围，并切换到范围起点所在月份；不满足规则时选择保持不变。返回选择是否已完成
+    pub fn apply_preset(&mut self, preset: RangePreset) -> bool {
+        let range = preset.resolve(self.today());
+        if !self.rules.allows_value(&DateValue::Range(range)) {
+            return false;
+        }
+        self.select_range(range)
+    }
+
+    /// 按选择模式解析粘贴的文本并选中，见 [`DateFormat::parse_value`]；不满足规则时返回
+    /// [`ParseDateError::NotAllowed`]，选择保持不变。返回选择是否已完成
+    pub fn paste(&mut self, text: &str, format: &DateFormat) -> Result<bool, ParseDateError> {
+        let value = format.parse_value(text, self.selection_mode)?;
+        if !self.rules.allows_value(&value) {
+            return Err(ParseDateError::NotAllowed(text.trim().to_string()));
+        }
+        Ok(match value {
+            DateValue::Date(date) => {
+                self.select_date(date);
+                true
+            }
+            DateValue::Week(week) => {
+                self.select_date(week.first_day());
+                true
+            }
+            DateValue::Range(range) => self.select_range(range),
+            DateValue::Comparison { primary, compare } => {
+                self.range_anchor = None;
+                self.current_month = first_of_month(primary.start);
+                self.selected_range = Some(primary);
+                // 对比范围与当前方式推导出的不同时改为手动对比，保证取值与粘贴的一致
+                if self.compare_mode.derive(primary) != Some(compare) {
+                    self.compare_mode = CompareMode::Custom;
+                }
+                self.custom_compare_range = Some(compare);
+                self.compare_target = CompareTarget::Primary;
+                true
+            }
+        })
+    }
+
+    /// 整段写入当前的目标范围，并切换到起点所在月份
+    fn select_range(&mut self, range: DateRange) -> bool {
+        self.range_anchor = None;
+        self.current_month = first_of_month(range.start);
+        self.finish_range(range)
+    }
+
+    /// 选中今天：单选模式选中今天，范围模式应用「今天」快捷范围；今天不满足规则时选择保持不变。
+    /// 返回选择是否已完成
+    pub fn select_today(&mut self) -> bool {
+        match self.selection_mode {
+            SelectionMode::Single | SelectionMode::Week => {
+                let today = self.today();
+                if !self.rules.is_allowed(&today) {
+                    return false;
+                }
+                self.select_date(today);
+                true
+            }
+            SelectionMode::Range | SelectionMode::Comparison => self.apply_preset(RangePreset::Today),
+        }
+    }
+
+    /// 选中 `date` 所在的整周：选周模式选中该 ISO 周，范围模式选中网格中同一行的 7 天，
+    /// 两端收缩到规则允许的日期；单选模式或整周都不可选时不做处理。返回选择是否已完成
+    pub fn select_week(&mut self, date: NaiveDate) -> bool {
+        match self.selection_mode {
+            SelectionMode::Single => false,
+            SelectionMode::Week => {
+                if !self.rules.allows_any(Week::containing(date).range()) {
+                    return false;
+                }
+                self.selected_date = Some(date);
+                true
+            }
+            SelectionMode::Range | SelectionMode::Comparison => {
+                let offset = date.weekday().days_since(self.week_start()) as i64;
+                let mut allowed = (-offset..7 - offset)
+                    .filter_map(|days| add_days(date, days))
+                    .filter(|day| self.rules.is_allowed(day));
+                let Some(start) = allowed.next() else {
+                    return false;
+                };
+                let end = allowed.next_back().unwrap_or(start);
+                self.range_anchor = None;
+                self.finish_range(DateRange::new(start, end))
+            }
+        }
+    }
+
+    /// 清除所有选择，取值变为 `None`
+    pub fn clear(&mut self) {
+        self.selected_date = None;
+        self.selected_range = None;
+        self.custom_compare_range = None;
+        self.range_anchor = None;
+        self.compare_target = CompareTarget::Primary;
+    }
+
+    /// 把一段完整的范围写入当前的目标（主范围或对比范围）
+    fn finish_range(&mut self, range: DateRange) -> bool {
+        if self.selection_mode == SelectionMode::Comparison
+            && self.compare_target == CompareTarget::Compare
+        {
+            self.custom_compare_range = Some(range);
+            self.compare_target = CompareTarget::Primary;
+            return true;
+        }
+
+        self.selected_range = Some(range);
+        let needs_compare = self.selection_mode == SelectionMode::Comparison
+            && self.compare_mode == CompareMode::Custom
+            && self.custom_compare_range.is_none();
+        if needs_compare {
+            self.compare_target = CompareTarget::Compare;
+        }
+        !needs_compare
+    }
+
+    /// 对比模式下生效的对比范围：手动选择的，或由主范围推导的
+    pub fn compare_range(&self) -> Option<DateRange> {
+        if self.selection_mode != SelectionMode::Comparison {
+            return None;
+        }
+        match self.compare_mode {
+            CompareMode::Custom => self.custom_compare_range,
+            mode => self.selected_range.and_then(|primary| mode.derive(primary)),
+        }
+    }
+
+    /// 与当前选中范围完全一致的快捷选项
+    pub fn active_preset(&self) -> Option<RangePreset> {
+        let today = self.today();
+        let range = self.selected_range?;
+        RangePreset::ALL
+            .into_iter()
+            .find(|preset| preset.resolve(today) == range)
+    }
+
+    /// 当前选择模式下的取值
+    pub fn value(&self) -> Option<DateValue> {
+        match self.selection_mode {
+            SelectionMode::Single => self.selected_date.map(DateValue::Date),
+            SelectionMode::Week => self.selected_week().map(DateValue::Week),
+            SelectionMode::Range => self.selected_range.map(DateValue::Range),
+            SelectionMode::Comparison => Some(DateValue::Comparison {
+                primary: self.selected_range?,
+                compare: self.compare_range()?,
+            }),
+        }
+    }
+
+    /// 取值在时钟所在时区覆盖的时间段 `[start, end)`，例如上海的 2025-03-01 为
+    /// `2025-03-01T00:00:00+08:00` 到 `2025-03-02T00:00:00+08:00`
+    pub fn value_instants(&self) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        self.value().map(|value| self.clock.instants(value.span()))
+    }
+
+    pub fn is_working_day(&self, date: &NaiveDate) -> bool {
+        self.working_days.is_working_day(date)
+    }
+
+    /// 前后移动 `days` 个工作日，不计起始日；超出范围或没有工作日时返回 `None`
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        self.working_days.add(date, days)
+    }
+
+    pub fn sub_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        self.working_days.add(date, days.checked_neg()?)
+    }
+
+    /// 日期之后的第一个工作日
+    pub fn next_working_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.working_days.next_working_day(date)
+    }
+
+    /// 范围内（包含两端）的工作日数
+    pub fn count_working_days(&self, range: DateRange) -> i64 {
+        self.working_days.count(range)
+    }
+
+    /// 选周模式下选中日期所在的 ISO 周
+    pub fn selected_week(&self) -> Option<Week> {
+        self.selected_date.map(Week::containing)
+    }
+
+    /// 日期在网格中能否被点击选中；选周模式下整周一起判断，周内还有一天可选即可
+    pub fn is_selectable(&self, date: &NaiveDate) -> bool {
+        match self.selection_mode {
+            SelectionMode::Week => self.rules.allows_any(Week::containing(*date).range()),
+            _ => {
+                (self.is_current_month(date) || self.grid_options.select_adjacent_days)
+                    && self.rules.is_allowed(date)
+            }
+        }
+    }
+
+    pub fn is_today(&self, date: &NaiveDate) -> bool {
+        *date == self.today()
+    }
+
+    /// 单选模式下的选中日期，选周模式下选中周的每一天，或范围模式下主范围的端点（包括进行中的起点）
+    pub fn is_selected(&self, date: &NaiveDate) -> bool {
+        match self.selection_mode {
+            SelectionMode::Single => self.selected_date == Some(*date),
+            SelectionMode::Week => self.selected_week() == Some(Week::containing(*date)),
+            SelectionMode::Range | SelectionMode::Comparison => {
+                self.is_endpoint(CompareTarget::Primary, self.selected_range, date)
+            }
+        }
+    }
+
+    /// 范围模式下位于主范围之内，重新选择主范围时不显示旧范围
+    pub fn is_in_range(&self, date: &NaiveDate) -> bool {
+        matches!(self.selection_mode, SelectionMode::Range | SelectionMode::Comparison)
+            && !self.is_editing(CompareTarget::Primary)
+            && self.selected_range.is_some_and(|range| range.contains(date))
+    }
+
+    /// 对比范围的端点（包括手动选择进行中的起点）
+    pub fn is_compare_endpoint(&self, date: &NaiveDate) -> bool {
+        self.selection_mode == SelectionMode::Comparison
+            && self.is_endpoint(CompareTarget::Compare, self.compare_range(), date)
+    }
+
+    pub fn is_in_compare_range(&self, date: &NaiveDate) -> bool {
+        !self.is_editing(CompareTarget::Compare)
+            && self.compare_range().is_some_and(|range| range.contains(date))
+    }
+
+    /// 范围选择进行中时从起点到鼠标所在日期的预览范围，拖动或第二次点击之前显示
+    pub fn preview_range(&self) -> Option<DateRange> {
+        self.range_anchor
+            .zip(self.range_hover)
+            .map(|(anchor, hover)| DateRange::new(anchor, hover))
+    }
+
+    /// 位于预览范围之内，预览的是主范围还是对比范围由 `compare_target` 决定
+    pub fn is_in_preview(&self, date: &NaiveDate) -> bool {
+        self.preview_range().is_some_and(|range| range.contains(date))
+    }
+
+    /// 该目标的范围正在选择中（已点击第一个端点）
+    fn is_editing(&self, target: CompareTarget) -> bool {
+        self.range_anchor.is_some() && self.compare_target == target
+    }
+
+    fn is_endpoint(&self, target: CompareTarget, range: Option<DateRange>, date: &NaiveDate) -> bool {
+        if self.is_editing(target) {
+            return self.range_anchor == Some(*date);
+        }
+        range.is_some_and(|range| range.start == *date || range.end == *date)
+    }
+
+    pub fn is_current_month(&self, date: &NaiveDate) -> bool {
+        date.year() == self.current_month.year() && date.month() == self.current_month.month()
+    }
+}
+
+/// 月历网格的缓存，同一月份、同样的网格选项只计算一次。
+///
+/// 网格只取决于月份与网格选项（含实际生效的每周起始日），与选中状态和「今天」无关，
+/// 因此选中、悬停等重绘都能直接复用。
+#[derive(Clone, Debug, Default)]
+pub struct GridCache {
+    grids: HashMap<(NaiveDate, GridOptions), Rc<[Option<NaiveDate>]>>,
+}
+
+impl GridCache {
+    /// 超过这么多个网格时整体清空，避免长时间翻页后无限增长
+    const CAPACITY: usize = 24;
+
+    /// 与 [`Calendar::get_month_days`] 相同的网格，命中缓存时不重新计算
+    pub fn get(&mut self, calendar: &Calendar) -> Rc<[Option<NaiveDate>]> {
+        let options = GridOptions {
+            week_start: calendar.week_start(),
+            ..calendar.grid_options
+        };
+        let key = (first_of_month(calendar.current_month), options);
+        if let Some(grid) = self.grids.get(&key) {
+            return grid.clone();
+        }
+        if self.grids.len() >= Self::CAPACITY {
+            self.grids.clear();
+        }
+        let grid: Rc<[Option<NaiveDate>]> = calendar.get_month_days().into();
+        self.grids.insert(key, grid.clone());
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.grids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.grids.is_empty()
+    }
+}
+
+/// 纵向连续滚动时的月份序列：锚点月份前后各 [`MonthList::SPAN`] 个月，并受最早/最晚日期限制。
+///
+/// 两个方向都足够长，滚动起来相当于无限；长度固定，滚动位置不会因为追加月份而跳动。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MonthList {
+    first: NaiveDate,
+    len: usize,
+}
+
+impl MonthList {
+    /// 锚点前后各延伸的月数
+    pub const SPAN: i32 = 1200;
+
+    pub fn around(anchor: NaiveDate, rules: &DateRules) -> Self {
+        let mut first = shift_month(anchor, -Self::SPAN);
+        let mut last = shift_month(anchor, Self::SPAN);
+        if let Some(min_date) = rules.min_date {
+            first = first.max(first_of_month(min_date));
+        }
+        if let Some(max_date) = rules.max_date {
+            last = last.min(first_of_month(max_date));
+        }
+        let len = months_between(first, last.max(first)) as usize + 1;
+        Self { first, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 至少包含一个月，总是返回 `false`
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 第 `ix` 个月的 1 号
+    pub fn month(&self, ix: usize) -> NaiveDate {
+        shift_month(self.first, ix as i32)
+    }
+
+    /// 日期所在月份的序号，不在序列内时返回 `None`
+    pub fn index_of(&self, date: NaiveDate) -> Option<usize> {
+        let ix = months_between(self.first, first_of_month(date));
+        usize::try_from(ix).ok().filter(|ix| *ix < self.len)
+    }
+}
+
+/// 从 `from` 所在月份到 `to` 所在月份相差的月数
+fn months_between(from: NaiveDate, to: NaiveDate) -> i64 {
+    (to.year() as i64 - from.year() as i64) * 12 + to.month() as i64 - from.month() as i64
+}
+
+/// 日期所在月份的 1 号
+pub fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// 日期所在月份的天数
+pub fn days_in_month(date: NaiveDate) -> u32 {
+    (28..=31)
+        .rev()
+        .find(|&day| date.with_day(day).is_some())
+        .unwrap_or(28)
+}
+
+/// 加减天数，超出 `NaiveDate` 范围时返回 `None`
+pub fn add_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    if days >= 0 {
+        date.checked_add_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    }
+}
+
+/// 按月平移，结果总是某月 1 号，越界时夹到可表示范围的首/末月
+pub fn shift_month(date: NaiveDate, months: i32) -> NaiveDate {
+    let first_day = first_of_month(date);
+    let shifted = if months >= 0 {
+        first_day.checked_add_months(Months::new(months.unsigned_abs()))
+    } else {
+        first_day.checked_sub_months(Months::new(months.unsigned_abs()))
+    };
+    shifted.unwrap_or_else(|| {
+        if months >= 0 {
+            first_of_month(NaiveDate::MAX)
+        } else {
+            first_of_month(NaiveDate::MIN)
+        }
+    })
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Days, FixedOffset, Local, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::range::DateRange;
+use crate::zone;
+
+/// 「今天」的来源，决定今日高亮和快捷范围的计算基准
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Clock {
+    /// 本地系统时间
+    #[default]
+    System,
+    /// 固定日期，用于测试或回放；按 UTC 换算时刻
+    Fixed(NaiveDate),
+    /// 系统时间在指定 IANA 时区中的日期，例如 `Asia/Shanghai`
+    Zone(Tz),
+}
+
+impl Clock {
+    pub fn today(&self) -> NaiveDate {
+        match self {
+            Clock::System => Local::now().date_naive(),
+            Clock::Fixed(date) => *date,
+            Clock::Zone(tz) => Utc::now().with_timezone(tz).date_naive(),
+        }
+    }
+
+    /// 距离下一个 0 点（「今天」变化）的时长；固定日期的时钟永远不变，返回 `None`
+    pub fn until_next_day(&self) -> Option<Duration> {
+        match self {
+            Clock::System => until_next_day_in(&Local),
+            Clock::Fixed(_) => None,
+            Clock::Zone(tz) => until_next_day_in(tz),
+        }
+    }
+
+    /// 配置的 IANA 时区
+    pub fn zone(&self) -> Option<Tz> {
+        match self {
+            Clock::Zone(tz) => Some(*tz),
+            Clock::System | Clock::Fixed(_) => None,
+        }
+    }
+
+    /// 范围在本时钟所在时区覆盖的时间段 `[start, end)`
+    pub fn instants(&self, range: DateRange) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+        match self {
+            Clock::System => {
+                let (start, end) = zone::range_instants(range, &Local);
+                (start.fixed_offset(), end.fixed_offset())
+            }
+            Clock::Fixed(_) => {
+                let (start, end) = zone::range_instants(range, &Utc);
+                (start.fixed_offset(), end.fixed_offset())
+            }
+            Clock::Zone(tz) => {
+                let (start, end) = zone::range_instants(range, tz);
+                (start.fixed_offset(), end.fixed_offset())
+            }
+        }
+    }
+}
+
+fn until_next_day_in<Z: TimeZone>(zone: &Z) -> Option<Duration> {
+    let now = Utc::now().with_timezone(zone);
+    let tomorrow = now.date_naive().checked_add_days(Days::new(1))?;
+    (zone::start_of_day(tomorrow, zone) - now).to_std().ok()
+}
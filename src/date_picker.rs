@@ -0,0 +1,1745 @@
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Weekday};
+use chrono_tz::Tz;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::calendar::{Calendar, GridCache, GridOptions, MonthList, first_of_month};
+use crate::business::WorkingDays;
+use crate::clock::Clock;
+use crate::day_decorator::{DayCellState, DayDecoration, DayDecorator};
+use crate::day_tooltip::{DayItem, day_tooltip};
+use crate::format::{DateFormat, ParseDateError};
+use crate::locale::Locale;
+use crate::period::Week;
+use crate::popup;
+use crate::range::{CompareMode, RangePreset};
+use crate::relative::{RelativeStyle, describe_relative, describe_relative_range};
+use crate::rollover::watch_clock;
+use crate::rules::DateRules;
+use crate::selection::{CompareTarget, DateValue, SelectionMode};
+use crate::theme::{self, ActiveTheme, Theme, watch_appearance};
+use crate::zone::zone_abbreviation;
+
+actions!(
+    date_picker,
+    [
+        MoveLeft,
+        MoveRight,
+        MoveUp,
+        MoveDown,
+        PreviousMonth,
+        NextMonth,
+        PreviousYear,
+        NextYear,
+        SelectFocused,
+        Dismiss,
+        CopyValue,
+        PasteValue,
+    ]
+);
+
+const KEY_CONTEXT: &str = "DatePicker";
+/// 选周模式下网格的每一行，悬停时整行高亮
+const WEEK_ROW_GROUP: &str = "week-row";
+/// 连续滚动模式下每个月的高度：月份标题加固定 6 行日期，各月等高才能交给 `uniform_list`
+const SCROLL_MONTH_HEIGHT: f32 = 40.0 * 7.0;
+/// 连续滚动模式的可视高度，大约一个半月
+const SCROLL_VIEW_HEIGHT: f32 = 420.0;
+
+/// 拖动选择范围时，光标停在翻页按钮上或移出网格左右边缘后，每隔这么久翻一次
+const AUTO_ADVANCE_INTERVAL: Duration = Duration::from_millis(600);
+
+/// 一帧内所有网格共用的数据：「今天」只取一次，日期格的各个回调只创建一次
+struct GridFrame {
+    today: NaiveDate,
+    pick: Rc<dyn Fn(&NaiveDate, &mut Window, &mut App)>,
+    /// 鼠标移到日期上，更新范围预览
+    hover: Rc<dyn Fn(&NaiveDate, &mut Window, &mut App)>,
+    /// 拖动选择范围时在日期上松开
+    drop: Rc<dyn Fn(&NaiveDate, &mut Window, &mut App)>,
+    /// 右键日期，带上鼠标位置
+    open_menu: Rc<dyn Fn(&(NaiveDate, Point<Pixels>), &mut Window, &mut App)>,
+}
+
+/// 应用登记的右键菜单项的回调，参数为右键的日期
+type DayMenuHandler = Rc<dyn Fn(NaiveDate, &mut Window, &mut App)>;
+
+/// 在日期上按下并拖动时的拖动数据，起点记在 [`Calendar::range_anchor`] 中
+struct DayDrag;
+
+/// 拖动选择范围时的自动翻月状态
+#[derive(Default)]
+struct DragState {
+    /// 光标在网格左侧（连续滚动模式下为上方）为 -1、右侧（下方）为 1，否则为 0
+    edge: i32,
+    /// 光标所在翻页按钮的步长（月数）
+    arrow: Option<i32>,
+    /// 正在自动翻月的步长与定时任务
+    advance: Option<(i32, Task<()>)>,
+}
+
+/// 注册日期选择器的键盘绑定，应用启动时调用一次
+pub fn init(cx: &mut App) {
+    theme::init(cx);
+    cx.bind_keys([
+        KeyBinding::new("left", MoveLeft, Some(KEY_CONTEXT)),
+        KeyBinding::new("right", MoveRight, Some(KEY_CONTEXT)),
+        KeyBinding::new("up", MoveUp, Some(KEY_CONTEXT)),
+        KeyBinding::new("down", MoveDown, Some(KEY_CONTEXT)),
+        KeyBinding::new("pageup", PreviousMonth, Some(KEY_CONTEXT)),
+        KeyBinding::new("pagedown", NextMonth, Some(KEY_CONTEXT)),
+        KeyBinding::new("shift-pageup", PreviousYear, Some(KEY_CONTEXT)),
+        KeyBinding::new("shift-pagedown", NextYear, Some(KEY_CONTEXT)),
+        KeyBinding::new("enter", SelectFocused, Some(KEY_CONTEXT)),
+        KeyBinding::new("space", SelectFocused, Some(KEY_CONTEXT)),
+        KeyBinding::new("escape", Dismiss, Some(KEY_CONTEXT)),
+        KeyBinding::new("secondary-c", CopyValue, Some(KEY_CONTEXT)),
+        KeyBinding::new("secondary-v", PasteValue, Some(KEY_CONTEXT)),
+    ]);
+}
+
+/// 日历的呈现方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// 输入框触发，日历以浮层弹出
+    #[default]
+    Popup,
+    /// 始终显示日历本体，没有输入框、浮层定位和遮罩，适合嵌入侧边栏
+    Inline,
+    /// 类似移动端日历，各月纵向排列、向前向后连续滚动，只渲染可见的月份
+    Scroll,
+}
+
+/// 带输入框触发器和弹出日历的日期选择器。
+///
+/// 作为独立的 Entity 使用，选择结果通过 [`DatePickerEvent`] 通知订阅者：
+///
+/// ```ignore
+/// let picker = cx.new(|cx| DatePicker::new(cx).label("开始日期"));
+/// cx.subscribe(&picker, |this, _, event: &DatePickerEvent, cx| { /* ... */ }).detach();
+/// ```
+pub struct DatePicker {
+    id: SharedString,
+    focus_handle: FocusHandle,
+    display_mode: DisplayMode,
+    calendar: Calendar,
+    format: DateFormat,
+    label: Option<SharedString>,
+    is_open: bool,
+    show_above: bool,
+    show_footer: bool,
+    show_working_days: bool,
+    locale: Locale,
+    /// 输入框下方相对今天的说明文字，`None` 时不显示
+    caption: Option<RelativeStyle>,
+    /// 应用提供的日期悬停提示内容，列在节假日之后
+    day_items: Option<Rc<dyn Fn(NaiveDate) -> Vec<DayItem>>>,
+    /// 按优先级从低到高排列，优先级相同的保持添加顺序
+    decorators: Vec<Rc<dyn DayDecorator>>,
+    /// 应用登记的右键菜单项，排在内置项之后
+    menu_items: Vec<(SharedString, DayMenuHandler)>,
+    /// 打开右键菜单的日期与鼠标位置
+    context_menu: Option<(NaiveDate, Point<Pixels>)>,
+    require_confirm: bool,
+    /// 确认模式下第一次暂定选择之前的日历，确定之前的点击都只是暂定选择
+    committed: Option<Calendar>,
+    /// 渲染时复用的月历网格，只在翻页或改变网格选项后重新计算
+    grid_cache: RefCell<GridCache>,
+    /// 连续滚动模式的月份序列，第一次渲染时以当前月份为锚点生成
+    months: Option<MonthList>,
+    scroll_handle: UniformListScrollHandle,
+    /// 最近一次程序滚动的目标月份与当时的滚动位置；滚动生效前用它决定吸顶的月份标题
+    scroll_target: Option<(usize, Pixels)>,
+    drag: DragState,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DatePickerEvent {
+    /// 选中的日期或范围发生变化
+    Changed(Option<DateValue>),
+    Opened,
+    Closed,
+    /// 在右键菜单中选择了「在这天新建日程」
+    NewEvent(NaiveDate),
+}
+
+impl EventEmitter<DatePickerEvent> for DatePicker {}
+
+impl Focusable for DatePicker {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl DatePicker {
+    pub fn new(cx: &mut App) -> Self {
+        Self {
+            id: "date-picker".into(),
+            focus_handle: cx.focus_handle(),
+            display_mode: DisplayMode::Popup,
+            calendar: Calendar::new(),
+            format: DateFormat::default(),
+            label: None,
+            is_open: false,
+            show_above: false,
+            show_footer: false,
+            show_working_days: false,
+            locale: Locale::default(),
+            caption: None,
+            day_items: None,
+            decorators: Vec::new(),
+            menu_items: Vec::new(),
+            context_menu: None,
+            require_confirm: false,
+            committed: None,
+            grid_cache: RefCell::default(),
+            months: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            scroll_target: None,
+            drag: DragState::default(),
+        }
+    }
+
+    /// 组件标识，用作各交互元素调试选择器的前缀，例如 `"{id}-trigger"`
+    pub fn id(mut self, id: impl Into<SharedString>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn display_mode(mut self, mode: DisplayMode) -> Self {
+        self.display_mode = mode;
+        self
+    }
+
+    /// 输入框上方的说明文字
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// 弹层在输入框上方展开
+    pub fn show_above(mut self, show_above: bool) -> Self {
+        self.show_above = show_above;
+        self
+    }
+
+    /// 范围模式下在选中范围旁显示其中的工作日数，例如「12 个工作日」
+    pub fn show_working_days(mut self, show_working_days: bool) -> Self {
+        self.show_working_days = show_working_days;
+        self
+    }
+
+    /// 说明文字等使用的语言
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 星期标题、菜单与说明文字使用的语言
+    pub(crate) fn current_locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// 在输入框下方显示取值相对今天的说明，例如「3 天后」「上周二」
+    pub fn relative_caption(mut self, style: RelativeStyle) -> Self {
+        self.caption = Some(style);
+        self
+    }
+
+    /// 日期格悬停提示中的日程、备注等；节假日由 [`WorkingDays`] 自动列出。
+    ///
+    /// 每次渲染对每个日期调用一次，应只做查表之类的轻量工作；没有任何项的日期不显示提示：
+    ///
+    /// ```ignore
+    /// DatePicker::new(cx).day_tooltip(move |date| {
+    ///     meetings.get(&date).cloned().unwrap_or_default()
+    /// })
+    /// ```
+    pub fn day_tooltip(mut self, items: impl Fn(NaiveDate) -> Vec<DayItem> + 'static) -> Self {
+        self.day_items = Some(Rc::new(items));
+        self
+    }
+
+    /// 添加日期格装饰器，见 [`DayDecorator`]；可多次调用，按优先级叠加
+    pub fn decorator(mut self, decorator: impl DayDecorator + 'static) -> Self {
+        let ix = self
+            .decorators
+            .partition_point(|existing| existing.priority() <= decorator.priority());
+        self.decorators.insert(ix, Rc::new(decorator));
+        self
+    }
+
+    /// 在日期的右键菜单末尾添加一项，点击时以该日期调用 `handler`
+    pub fn context_menu_item(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(NaiveDate, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.menu_items.push((label.into(), Rc::new(handler)));
+        self
+    }
+
+    /// 工作日计数所用的周末与节假日
+    pub fn working_days(mut self, working_days: WorkingDays) -> Self {
+        self.calendar.working_days = working_days;
+        self
+    }
+
+    /// 在日期网格下方显示「今天」「清除」按钮
+    pub fn show_footer(mut self, show_footer: bool) -> Self {
+        self.show_footer = show_footer;
+        self
+    }
+
+    /// 确认模式：点击只做暂定选择，点「确定」才生效，「取消」或点击遮罩恢复原值
+    pub fn require_confirm(mut self, require_confirm: bool) -> Self {
+        self.require_confirm = require_confirm;
+        self
+    }
+
+    /// 输入框中显示日期所用的格式，默认 ISO 8601
+    pub fn date_format(mut self, format: DateFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// 相邻月份日期的显示、选择与末尾空行的处理方式
+    pub fn grid_options(mut self, options: GridOptions) -> Self {
+        self.calendar.grid_options = options;
+        self
+    }
+
+    /// 最早/最晚日期与禁用日期
+    pub fn rules(mut self, rules: DateRules) -> Self {
+        self.calendar.rules = rules;
+        self.months = None;
+        self
+    }
+
+    /// 单选或范围选择；范围模式的弹层并排显示两个月，并带快捷范围侧边栏
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.calendar.selection_mode = mode;
+        self
+    }
+
+    /// 对比模式下对比范围的来源
+    pub fn compare_mode(mut self, mode: CompareMode) -> Self {
+        self.calendar.compare_mode = mode;
+        self
+    }
+
+    /// 「今天」的来源，同时把当前月份与默认选中日期重置为该时钟的今天
+    pub fn clock(mut self, clock: Clock) -> Self {
+        let today = clock.today();
+        self.calendar.clock = clock;
+        self.calendar.current_month = first_of_month(today);
+        self.calendar.selected_date = Some(today);
+        self
+    }
+
+    /// 按 IANA 时区计算「今天」与取值的时刻，输入框中显示时区缩写
+    pub fn time_zone(self, tz: Tz) -> Self {
+        self.clock(Clock::Zone(tz))
+    }
+
+    /// 运行时更换时钟或时区，保留已选的值；跨天定时随之按新时钟重新安排
+    pub fn set_clock(&mut self, clock: Clock, cx: &mut Context<Self>) {
+        self.calendar.clock = clock;
+        watch_clock(clock, cx);
+        cx.notify();
+    }
+
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    pub fn selected_date(&self) -> Option<NaiveDate> {
+        self.calendar.selected_date
+    }
+
+    pub fn value(&self) -> Option<DateValue> {
+        self.calendar.value()
+    }
+
+    /// 已提交的取值：确认模式下忽略尚未「确定」的暂定选择
+    pub fn committed_value(&self) -> Option<DateValue> {
+        match &self.committed {
+            Some(committed) => committed.value(),
+            None => self.calendar.value(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn is_shown_above(&self) -> bool {
+        self.show_above
+    }
+
+    /// 开启 [`show_working_days`](Self::show_working_days) 时选中范围内的工作日数
+    pub fn working_day_count(&self) -> Option<i64> {
+        if !self.show_working_days {
+            return None;
+        }
+        match self.calendar.value()? {
+            DateValue::Range(range) => Some(self.calendar.count_working_days(range)),
+            _ => None,
+        }
+    }
+
+    /// 输入框下方的说明文字，未开启或没有取值时为 `None`
+    pub fn caption(&self) -> Option<String> {
+        let style = self.caption?;
+        let today = self.calendar.today();
+        Some(match self.calendar.value()? {
+            DateValue::Date(date) => describe_relative(date, today, self.locale, style),
+            value => describe_relative_range(value.span(), today, self.locale, style),
+        })
+    }
+
+    /// 取值在选择器时区覆盖的时间段 `[start, end)`
+    pub fn value_instants(&self) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        self.calendar.value_instants()
+    }
+
+    /// 配置了时区时，取值起始日（没有取值时为今天）所用的时区缩写，例如 `EST`
+    pub fn zone_abbreviation(&self) -> Option<String> {
+        let tz = self.calendar.clock.zone()?;
+        let date = self
+            .calendar
+            .value()
+            .map_or_else(|| self.calendar.today(), |value| value.span().start);
+        Some(zone_abbreviation(tz, date))
+    }
+
+    /// 输入框中显示的文字
+    pub fn format_date(&self) -> String {
+        match self.calendar.value() {
+            Some(value) => self.format.format_value(&value),
+            None => match self.calendar.selection_mode {
+                SelectionMode::Single => "选择日期".to_string(),
+                SelectionMode::Week => "选择周".to_string(),
+                SelectionMode::Range | SelectionMode::Comparison => "选择日期范围".to_string(),
+            },
+        }
+    }
+
+    pub fn open(&mut self, cx: &mut Context<Self>) {
+        if !self.is_open {
+            self.is_open = true;
+            cx.emit(DatePickerEvent::Opened);
+            cx.notify();
+        }
+    }
+
+    /// 关闭弹层；确认模式下未确定的暂定选择会被丢弃
+    pub fn close(&mut self, cx: &mut Context<Self>) {
+        if self.is_open {
+            self.is_open = false;
+            self.discard_tentative();
+            self.calendar.range_anchor = None;
+            self.calendar.focused_date = None;
+            self.calendar.compare_target = CompareTarget::Primary;
+            self.context_menu = None;
+            cx.emit(DatePickerEvent::Closed);
+            cx.notify();
+        }
+    }
+
+    pub fn toggle(&mut self, cx: &mut Context<Self>) {
+        if self.is_open {
+            self.close(cx);
+        } else {
+            self.open(cx);
+        }
+    }
+
+    /// 按选择模式处理一次日期点击，选择完成后关闭弹层，取值变化时发出 [`DatePickerEvent::Changed`]
+    pub fn select(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        self.begin_tentative();
+        let previous = self.calendar.value();
+        let completed = self.calendar.pick(date);
+        self.calendar.focused_date = None;
+        self.finish_selection(previous, completed, cx);
+    }
+
+    /// 应用快捷范围，选择完成后关闭弹层
+    pub fn apply_preset(&mut self, preset: RangePreset, cx: &mut Context<Self>) {
+        self.begin_tentative();
+        let previous = self.calendar.value();
+        let completed = self.calendar.apply_preset(preset);
+        self.finish_selection(previous, completed, cx);
+    }
+
+    /// 选中某天所在的整周，见 [`Calendar::select_week`]
+    pub fn select_week(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        self.begin_tentative();
+        let previous = self.calendar.value();
+        let completed = self.calendar.select_week(date);
+        self.finish_selection(previous, completed, cx);
+    }
+
+    /// 把取值写入剪贴板：文字为输入框中的格式，元数据为 ISO 8601 表示，见 [`DateValue::to_iso_string`]
+    pub fn copy(&self, cx: &mut App) {
+        if let Some(value) = self.calendar.value() {
+            cx.write_to_clipboard(ClipboardItem::new_string_with_metadata(
+                self.format.format_value(&value),
+                value.to_iso_string(),
+            ));
+        }
+    }
+
+    /// 解析文本并选中，解析方式与 [`Calendar::paste`] 相同；失败或不满足规则时选择不变。
+    ///
+    /// 粘贴的是完整的取值，确认模式下也直接提交：丢弃尚未确定的暂定选择，取值与上次提交的不同时发出
+    /// [`DatePickerEvent::Changed`]
+    pub fn paste(&mut self, text: &str, cx: &mut Context<Self>) -> Result<(), ParseDateError> {
+        let mut calendar = self.calendar.clone();
+        let completed = calendar.paste(text, &self.format)?;
+        let previous = match self.committed.take() {
+            Some(committed) => committed.value(),
+            None => self.calendar.value(),
+        };
+        self.calendar = calendar;
+        self.calendar.focused_date = None;
+        let value = self.calendar.value();
+        if value != previous {
+            cx.emit(DatePickerEvent::Changed(value));
+        }
+        if completed {
+            self.close(cx);
+        }
+        cx.notify();
+        Ok(())
+    }
+
+    /// 本组件复制的内容在元数据中带有 ISO 8601 文本，优先使用，不受显示格式影响
+    fn paste_from_clipboard(&mut self, cx: &mut Context<Self>) {
+        let Some(item) = cx.read_from_clipboard() else {
+            return;
+        };
+        if let Some(iso) = item.metadata().cloned()
+            && self.paste(&iso, cx).is_ok()
+        {
+            return;
+        }
+        if let Some(text) = item.text() {
+            self.paste(&text, cx).ok();
+        }
+    }
+
+    pub fn select_today(&mut self, cx: &mut Context<Self>) {
+        self.begin_tentative();
+        let previous = self.calendar.value();
+        let completed = self.calendar.select_today();
+        self.finish_selection(previous, completed, cx);
+    }
+
+    /// 清除选择，取值变为 `None`
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.begin_tentative();
+        let previous = self.calendar.value();
+        self.calendar.clear();
+        self.finish_selection(previous, true, cx);
+    }
+
+    /// 确认模式下提交暂定选择并关闭弹层
+    pub fn confirm(&mut self, cx: &mut Context<Self>) {
+        if let Some(committed) = self.committed.take() {
+            self.emit_if_changed(committed.value(), cx);
+        }
+        self.close(cx);
+    }
+
+    /// 放弃暂定选择并关闭弹层
+    pub fn cancel(&mut self, cx: &mut Context<Self>) {
+        self.discard_tentative();
+        self.close(cx);
+        cx.notify();
+    }
+
+    /// 确认模式下，在第一次修改前记下已提交的日历
+    fn begin_tentative(&mut self) {
+        if self.require_confirm && self.committed.is_none() {
+            self.committed = Some(self.calendar.clone());
+        }
+    }
+
+    fn discard_tentative(&mut self) {
+        if let Some(committed) = self.committed.take() {
+            self.calendar = committed;
+        }
+    }
+
+    /// 确认模式下只更新暂定选择，否则发出变化事件并在选择完成时关闭弹层
+    fn finish_selection(&mut self, previous: Option<DateValue>, completed: bool, cx: &mut Context<Self>) {
+        self.emit_if_changed(previous, cx);
+        if completed && self.committed.is_none() {
+            self.close(cx);
+        }
+        cx.notify();
+    }
+
+    /// 切换对比范围的来源，切到自定义且尚无对比范围时接着选择对比范围
+    pub fn set_compare_mode(&mut self, mode: CompareMode, cx: &mut Context<Self>) {
+        self.begin_tentative();
+        let previous = self.calendar.value();
+        self.calendar.compare_mode = mode;
+        self.calendar.range_anchor = None;
+        self.calendar.compare_target = if mode == CompareMode::Custom
+            && self.calendar.custom_compare_range.is_none()
+        {
+            CompareTarget::Compare
+        } else {
+            CompareTarget::Primary
+        };
+        self.emit_if_changed(previous, cx);
+        cx.notify();
+    }
+
+    /// 自定义对比时，指定日历点击作用于主范围还是对比范围
+    pub fn set_compare_target(&mut self, target: CompareTarget, cx: &mut Context<Self>) {
+        self.calendar.compare_target = target;
+        self.calendar.range_anchor = None;
+        cx.notify();
+    }
+
+    fn emit_if_changed(&mut self, previous: Option<DateValue>, cx: &mut Context<Self>) {
+        // 暂定选择等到确定时再通知
+        if self.committed.is_some() {
+            return;
+        }
+        let value = self.calendar.value();
+        if value != previous {
+            cx.emit(DatePickerEvent::Changed(value));
+        }
+    }
+
+    /// 方向键等移动键盘光标；弹层关闭时先打开弹层
+    fn move_focus(&mut self, days: i64, cx: &mut Context<Self>) {
+        if self.open_if_closed(cx) {
+            return;
+        }
+        self.calendar.move_focus(days);
+        self.reveal_focused(cx);
+        cx.notify();
+    }
+
+    fn move_focus_months(&mut self, months: i32, cx: &mut Context<Self>) {
+        if self.open_if_closed(cx) {
+            return;
+        }
+        self.calendar.move_focus_months(months);
+        self.reveal_focused(cx);
+        cx.notify();
+    }
+
+    /// 与点击光标所在格子相同：按该日期所在月份的网格判断能否选中，选周模式下整周一起判断
+    fn select_focused(&mut self, cx: &mut Context<Self>) {
+        if self.open_if_closed(cx) {
+            return;
+        }
+        let focused = self.calendar.focused_date;
+        if let Some(date) = focused.filter(|date| self.calendar.month_view(*date).is_selectable(date)) {
+            self.select(date, cx);
+        }
+    }
+
+    fn dismiss(&mut self, cx: &mut Context<Self>) {
+        if self.context_menu.is_some() {
+            self.close_context_menu(cx);
+            return;
+        }
+        match self.display_mode {
+            DisplayMode::Popup => self.cancel(cx),
+            DisplayMode::Inline | DisplayMode::Scroll => {
+                self.calendar.focused_date = None;
+                cx.notify();
+            }
+        }
+    }
+
+    /// 连续滚动模式的月份序列
+    fn month_list(&mut self) -> MonthList {
+        if let Some(months) = self.months {
+            return months;
+        }
+        let months = MonthList::around(self.calendar.current_month, &self.calendar.rules);
+        self.months = Some(months);
+        self.scroll_to_index(months.index_of(self.calendar.current_month).unwrap_or_default());
+        months
+    }
+
+    fn scroll_to_index(&mut self, ix: usize) {
+        let offset = self.scroll_handle.0.borrow().base_handle.offset().y;
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        self.scroll_target = Some((ix, offset));
+    }
+
+    /// 连续滚动模式下把日期所在的月份滚动到顶部；超出可选范围的日期被忽略
+    pub fn scroll_to_month(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        if let Some(ix) = self.month_list().index_of(date) {
+            self.calendar.current_month = first_of_month(date);
+            self.scroll_to_index(ix);
+            cx.notify();
+        }
+    }
+
+    /// 翻到今天所在的月份，连续滚动模式下滚动过去；不改变选择
+    pub fn jump_to_today(&mut self, cx: &mut Context<Self>) {
+        self.show_month(self.calendar.today(), cx);
+    }
+
+    /// 翻到 `date` 所在的月份，连续滚动模式下滚动过去；不改变选择
+    pub fn show_month(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        if self.display_mode == DisplayMode::Scroll {
+            self.scroll_to_month(date, cx);
+        } else {
+            self.calendar.current_month = first_of_month(date);
+            cx.notify();
+        }
+    }
+
+    /// 在 `position` 处打开某天的右键菜单；规则禁用的日期不打开
+    pub fn open_context_menu(&mut self, date: NaiveDate, position: Point<Pixels>, cx: &mut Context<Self>) {
+        if !self.calendar.rules.is_allowed(&date) {
+            return;
+        }
+        self.context_menu = Some((date, position));
+        cx.notify();
+    }
+
+    pub fn close_context_menu(&mut self, cx: &mut Context<Self>) {
+        if self.context_menu.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// 打开了右键菜单的日期
+    pub fn context_menu_date(&self) -> Option<NaiveDate> {
+        self.context_menu.map(|(date, _)| date)
+    }
+
+    /// 吸顶标题对应的月份序号：程序滚动尚未生效时取滚动目标，否则按滚动位置计算
+    fn top_visible_month(&mut self) -> usize {
+        let offset = self.scroll_handle.0.borrow().base_handle.offset().y;
+        match self.scroll_target {
+            Some((ix, requested_at)) if offset == requested_at => ix,
+            _ => {
+                self.scroll_target = None;
+                let top = (-f32::from(offset) / SCROLL_MONTH_HEIGHT).max(0.0) as usize;
+                top.min(self.month_list().len() - 1)
+            }
+        }
+    }
+
+    /// 键盘光标移到另一个月时，连续滚动模式下把该月滚动到顶部
+    fn reveal_focused(&mut self, cx: &mut Context<Self>) {
+        if self.display_mode != DisplayMode::Scroll {
+            return;
+        }
+        if let Some(focused) = self.calendar.focused_date {
+            let months = self.month_list();
+            if months.index_of(focused) != Some(self.top_visible_month()) {
+                self.scroll_to_month(focused, cx);
+            }
+        }
+    }
+
+    /// 弹出模式下弹层未打开时打开它，返回是否打开了
+    fn open_if_closed(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.display_mode == DisplayMode::Popup && !self.is_open {
+            self.open(cx);
+            return true;
+        }
+        false
+    }
+
+    #[allow(dead_code)]
+    fn calculate_position(&mut self, window: &Window) {
+        let window_bounds = window.inner_window_bounds();
+        let bounds = window_bounds.get_bounds();
+        let window_height: f32 = bounds.size.height.into();
+        
+        let input_y = window_height / 2.0;
+        let input_height = 40.0;
+        let input_bottom = input_y + input_height / 2.0;
+        let calendar_height = 320.0;
+        let margin = 8.0;
+        
+        let space_below = window_height - input_bottom;
+        let space_above = input_y - input_height / 2.0;
+        self.show_above = space_below < calendar_height + margin && space_above >= calendar_height + margin;
+    }
+
+    fn render_trigger(&self, theme: Theme, cx: &mut Context<Self>) -> Stateful<Div> {
+        let content = match self.calendar.value() {
+            Some(DateValue::Comparison { primary, compare }) => div()
+                .flex_1()
+                .flex()
+                .flex_col()
+                .child(div().text_color(theme.text).child(self.format.format_range(primary)))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.compare_text)
+                        .child(format!("对比 {}", self.format.format_range(compare)))
+                ),
+            Some(DateValue::Range(range)) if self.show_working_days => div()
+                .flex_1()
+                .flex()
+                .flex_col()
+                .child(div().text_color(theme.text).child(self.format.format_range(range)))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child(format!("{} 个工作日", self.calendar.count_working_days(range)))
+                ),
+            value => div()
+                .flex_1()
+                .text_color(if value.is_some() {
+                    theme.text
+                } else {
+                    theme.text_placeholder
+                })
+                .child(self.format_date()),
+        };
+        let content = div()
+            .flex_1()
+            .flex()
+            .items_center()
+            .child(content)
+            .when_some(self.zone_abbreviation(), |this, abbreviation| {
+                this.child(
+                    div()
+                        .ml_2()
+                        .px_1()
+                        .rounded(px(2.0))
+                        .bg(theme.subheader_bg)
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child(abbreviation)
+                )
+            });
+
+        popup::trigger(&self.id, theme, content)
+            .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, window, cx| {
+                window.focus(&picker.focus_handle);
+                picker.toggle(cx);
+            }))
+    }
+
+    /// 点击时翻 `months` 个月；拖动选择范围时光标停在按钮上也会按此步长自动翻月
+    fn render_nav_button(
+        &self,
+        name: &'static str,
+        icon: &'static str,
+        height: f32,
+        months: i32,
+        theme: Theme,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        popup::nav_button(&self.id, name, icon, height, theme)
+            .on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                picker.calendar.shift_months(months);
+                cx.notify();
+            }))
+            .on_drag_move(cx.listener(move |picker, event: &DragMoveEvent<DayDrag>, _, cx| {
+                let arrow = if event.bounds.contains(&event.event.position) {
+                    Some(months)
+                } else if picker.drag.arrow == Some(months) {
+                    None
+                } else {
+                    picker.drag.arrow
+                };
+                if arrow != picker.drag.arrow {
+                    picker.drag.arrow = arrow;
+                    picker.update_auto_advance(cx);
+                }
+            }))
+    }
+
+    /// 拖动选择范围期间按光标位置启动、改向或停止自动翻月；拖动结束后定时任务自行停止
+    fn update_auto_advance(&mut self, cx: &mut Context<Self>) {
+        let months = self.drag.arrow.unwrap_or(self.drag.edge);
+        if months == 0 {
+            self.drag.advance = None;
+            return;
+        }
+        if self.drag.advance.as_ref().is_some_and(|(current, _)| *current == months) {
+            return;
+        }
+        let task = cx.spawn(async move |picker, cx| {
+            loop {
+                cx.background_executor().timer(AUTO_ADVANCE_INTERVAL).await;
+                let advanced = picker.update(cx, |picker, cx| {
+                    if !cx.has_active_drag() {
+                        picker.drag = DragState::default();
+                        return false;
+                    }
+                    picker.advance_months(months, cx);
+                    true
+                });
+                if !matches!(advanced, Ok(true)) {
+                    break;
+                }
+            }
+        });
+        self.drag.advance = Some((months, task));
+    }
+
+    /// 自动翻月：连续滚动模式下滚动相应的月数，否则翻页
+    fn advance_months(&mut self, months: i32, cx: &mut Context<Self>) {
+        if self.display_mode == DisplayMode::Scroll {
+            let last = self.month_list().len() as i32 - 1;
+            let ix = (self.top_visible_month() as i32 + months).clamp(0, last);
+            self.scroll_to_index(ix as usize);
+        } else {
+            self.calendar.shift_months(months);
+        }
+        cx.notify();
+    }
+
+    /// 鼠标移到日期上：范围选择进行中时更新预览
+    fn hover_date(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        if self.calendar.range_anchor.is_some() && self.calendar.range_hover != Some(date) {
+            self.calendar.range_hover = Some(date);
+            cx.notify();
+        }
+    }
+
+    /// 拖动后在日期上松开，以它作为范围的终点；在起点上松开视同单击，等待第二次点击
+    fn finish_drag(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        self.drag = DragState::default();
+        if self.calendar.range_anchor.is_some_and(|anchor| anchor != date) {
+            self.select(date, cx);
+        }
+    }
+
+    /// 拖动在日期格之外松开：放弃进行中的范围，起点与预览一并清除
+    fn cancel_drag(&mut self, cx: &mut Context<Self>) {
+        self.drag = DragState::default();
+        let anchor = self.calendar.range_anchor.take();
+        let hover = self.calendar.range_hover.take();
+        if anchor.is_some() || hover.is_some() {
+            cx.notify();
+        }
+    }
+
+    /// 弹层或内嵌日历的外层：日期格之外的松开（包括弹层之外）都取消拖动。
+    /// 日期格的 `on_drop` 先于外层触发并取走拖动数据，因此不会走到这里
+    fn cancel_drag_outside_days(&self, element: Div, cx: &mut Context<Self>) -> Div {
+        element
+            .on_drop(cx.listener(|picker, _: &DayDrag, _, cx| picker.cancel_drag(cx)))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                if cx.has_active_drag() {
+                    picker.cancel_drag(cx);
+                }
+            }))
+    }
+
+    /// 网格外层容器的拖动监听：光标移出边缘时自动翻月，连续滚动模式看上下边缘，其余看左右边缘
+    fn drag_edge_listener(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> impl Fn(&DragMoveEvent<DayDrag>, &mut Window, &mut App) + 'static {
+        let is_vertical = self.display_mode == DisplayMode::Scroll;
+        cx.listener(move |picker, event: &DragMoveEvent<DayDrag>, _, cx| {
+            let position = event.event.position;
+            let bounds = event.bounds;
+            let (before, after) = if is_vertical {
+                (position.y < bounds.top(), position.y > bounds.bottom())
+            } else {
+                (position.x < bounds.left(), position.x > bounds.right())
+            };
+            let edge = if before {
+                -1
+            } else if after {
+                1
+            } else {
+                0
+            };
+            if edge != picker.drag.edge {
+                picker.drag.edge = edge;
+                picker.update_auto_advance(cx);
+            }
+        })
+    }
+
+    /// 某天悬停提示的全部项：先是节假日，再是应用提供的项
+    fn tooltip_items(&self, date: NaiveDate, theme: Theme) -> Vec<DayItem> {
+        let mut items = Vec::new();
+        if self.calendar.working_days.holidays.contains(&date) {
+            let title = match self.locale {
+                Locale::Zh => "节假日",
+                Locale::En => "Holiday",
+            };
+            items.push(DayItem::new(title, theme.holiday_text));
+        }
+        if let Some(day_items) = &self.day_items {
+            items.extend(day_items(date));
+        }
+        items
+    }
+
+    /// 按优先级从低到高叠加所有装饰器
+    fn decorate(&self, date: NaiveDate, state: DayCellState, theme: &Theme) -> DayDecoration {
+        self.decorators
+            .iter()
+            .fold(DayDecoration::default(), |decoration, decorator| {
+                decoration.merge(decorator.decorate(date, state, theme))
+            })
+    }
+
+    /// `month` 是该网格所显示月份的日历，范围模式的右侧网格与 `self.calendar` 不同
+    fn render_day_cell(
+        &self,
+        month: &Calendar,
+        date: NaiveDate,
+        frame: &GridFrame,
+        theme: Theme,
+    ) -> Stateful<Div> {
+        let is_current = month.is_current_month(&date);
+        let is_today = date == frame.today;
+        let is_selected = self.calendar.is_selected(&date);
+        let is_compare_endpoint = self.calendar.is_compare_endpoint(&date) && !is_selected;
+        let is_in_range = self.calendar.is_in_range(&date) && !is_selected;
+        let is_in_compare_range = self.calendar.is_in_compare_range(&date) && !is_selected;
+        let is_selectable = month.is_selectable(&date);
+        let is_disabled = !month.rules.is_allowed(&date);
+        let is_focused = self.calendar.focused_date == Some(date);
+        let is_week_mode = self.calendar.selection_mode == SelectionMode::Week;
+        let is_range_mode = matches!(self.calendar.selection_mode, SelectionMode::Range | SelectionMode::Comparison);
+        let is_in_preview = self.calendar.is_in_preview(&date) && !is_selected;
+        let (preview_bg, preview_text) = match self.calendar.compare_target {
+            CompareTarget::Primary => (theme.range_bg, theme.range_text),
+            CompareTarget::Compare => (theme.compare_bg, theme.compare_text),
+        };
+        let tooltip_items = self.tooltip_items(date, theme);
+        let bg = if is_selected {
+            theme.accent
+        } else if is_compare_endpoint {
+            theme.compare_accent
+        } else if is_in_preview {
+            preview_bg
+        } else if is_in_range {
+            theme.range_bg
+        } else if is_in_compare_range {
+            theme.compare_bg
+        } else if is_today {
+            theme.today_bg
+        } else {
+            theme.surface
+        };
+        let text = if is_selected {
+            theme.accent_text
+        } else if is_compare_endpoint {
+            theme.compare_accent_text
+        } else if !is_current || is_disabled {
+            theme.text_disabled
+        } else if is_in_preview {
+            preview_text
+        } else if is_in_range {
+            theme.range_text
+        } else if is_in_compare_range {
+            theme.compare_text
+        } else if is_today {
+            theme.today_text
+        } else {
+            theme.text
+        };
+        let state = DayCellState {
+            is_today,
+            is_selected,
+            is_current_month: is_current,
+            is_disabled,
+        };
+        let decoration = self.decorate(date, state, &theme);
+        let is_decorated = !decoration.children.is_empty();
+
+        div()
+            .id(SharedString::from(format!("day-{date}")))
+            .debug_selector(|| format!("{}-day-{date}", self.id))
+            .flex_1()
+            .flex()
+            .items_center()
+            .justify_center()
+            // 装饰元素撑高整行，未装饰的日历保持 40px 行高
+            .when(is_decorated, |this| this.min_h(px(40.0)))
+            .when(!is_decorated, |this| this.h(px(40.0)))
+            .relative()
+            .when(is_selectable, |this| this.cursor_pointer())
+            .when(is_week_mode && is_selectable, |this| {
+                this.group_hover(WEEK_ROW_GROUP, move |style| style.bg(theme.day_hover))
+            })
+            .when(!is_week_mode, |this| {
+                this.hover(move |style| {
+                    if is_selectable {
+                        style.bg(theme.day_hover)
+                    } else {
+                        style
+                    }
+                })
+            })
+            .when(!tooltip_items.is_empty(), |this| {
+                this.tooltip(day_tooltip(&self.id, date, tooltip_items, self.locale))
+            })
+            .on_mouse_down(MouseButton::Left, {
+                let pick = frame.pick.clone();
+                move |_, window, cx| {
+                    if is_selectable {
+                        pick(&date, window, cx);
+                    }
+                }
+            })
+            .on_mouse_down(MouseButton::Right, {
+                let open_menu = frame.open_menu.clone();
+                move |event, window, cx| open_menu(&(date, event.position), window, cx)
+            })
+            .when(is_range_mode && is_selectable, |this| {
+                let hover = frame.hover.clone();
+                let drag_hover = frame.hover.clone();
+                let drop = frame.drop.clone();
+                this.on_hover(move |hovered, window, cx| {
+                    if *hovered {
+                        hover(&date, window, cx);
+                    }
+                })
+                .on_drag(DayDrag, |_, _, _, cx| cx.new(|_| EmptyView))
+                .on_drag_move(move |event: &DragMoveEvent<DayDrag>, window, cx| {
+                    if event.bounds.contains(&event.event.position) {
+                        drag_hover(&date, window, cx);
+                    }
+                })
+                .on_drop(move |_: &DayDrag, window, cx| drop(&date, window, cx))
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .justify_center()
+                    .relative()
+                    .min_w(px(32.0))
+                    .min_h(px(32.0))
+                    .when(is_decorated, |this| this.px_1().py_0p5())
+                    .rounded(px(4.0))
+                    .when(is_focused, |this| this.border_2().border_color(theme.focus_ring))
+                    .bg(decoration.background.unwrap_or(bg))
+                    .text_color(decoration.text_color.unwrap_or(text))
+                    .font_weight(if is_today || is_selected || is_compare_endpoint {
+                        FontWeight::SEMIBOLD
+                    } else {
+                        FontWeight::NORMAL
+                    })
+                    .child(date.day().to_string())
+                    .when(is_decorated, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .items_center()
+                                .text_xs()
+                                .font_weight(FontWeight::NORMAL)
+                                .children(decoration.children)
+                        )
+                    })
+            )
+    }
+
+    /// 右键菜单：内置项之后是应用登记的项，点击任一项后菜单关闭
+    fn render_context_menu(
+        &self,
+        date: NaiveDate,
+        position: Point<Pixels>,
+        theme: Theme,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let (new_event, copy_date, today, whole_week) = match self.locale {
+            Locale::Zh => ("在这天新建日程", "复制日期", "回到今天", "选中整周"),
+            Locale::En => ("New event on this day", "Copy date", "Jump to today", "Select whole week"),
+        };
+        let item = |name: &str, label: &'static str| popup::menu_item(&self.id, name.to_string(), label.into(), theme);
+
+        let mut items = vec![
+            item("new-event", new_event).on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                picker.close_context_menu(cx);
+                cx.emit(DatePickerEvent::NewEvent(date));
+            })),
+            item("copy-date", copy_date).on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                picker.close_context_menu(cx);
+                cx.write_to_clipboard(ClipboardItem::new_string(picker.format.format(date)));
+            })),
+            item("today", today).on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                picker.close_context_menu(cx);
+                picker.jump_to_today(cx);
+            })),
+        ];
+        if self.calendar.selection_mode != SelectionMode::Single {
+            items.push(item("select-week", whole_week).on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |picker, _, _, cx| {
+                    picker.close_context_menu(cx);
+                    picker.select_week(date, cx);
+                }),
+            ));
+        }
+        // 应用的回调可能再更新这个选择器，在监听之外调用
+        let this = cx.entity().downgrade();
+        items.extend(self.menu_items.iter().enumerate().map(|(ix, (label, handler))| {
+            let this = this.clone();
+            let handler = handler.clone();
+            popup::menu_item(&self.id, format!("item-{ix}"), label.clone(), theme).on_mouse_down(
+                MouseButton::Left,
+                move |_, window, cx| {
+                    this.update(cx, |picker, cx| picker.close_context_menu(cx)).ok();
+                    handler(date, window, cx);
+                },
+            )
+        }));
+
+        popup::context_menu(&self.id, position, items, theme, window, cx.listener(|picker, _, _, cx| {
+            picker.close_context_menu(cx);
+        }))
+    }
+
+    fn grid_frame(&self, cx: &mut Context<Self>) -> GridFrame {
+        GridFrame {
+            today: self.calendar.today(),
+            pick: Rc::new(cx.listener(|picker, date: &NaiveDate, _, cx| picker.select(*date, cx))),
+            hover: Rc::new(cx.listener(|picker, date: &NaiveDate, _, cx| picker.hover_date(*date, cx))),
+            drop: Rc::new(cx.listener(|picker, date: &NaiveDate, _, cx| picker.finish_drag(*date, cx))),
+            open_menu: Rc::new(cx.listener(|picker, (date, position): &(NaiveDate, Point<Pixels>), _, cx| {
+                picker.open_context_menu(*date, *position, cx);
+            })),
+        }
+    }
+
+    fn render_popup(&self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let frame = self.grid_frame(cx);
+        match self.calendar.selection_mode {
+            SelectionMode::Single | SelectionMode::Week => self.render_single_popup(&frame, theme, cx),
+            SelectionMode::Range | SelectionMode::Comparison => self.render_range_popup(&frame, theme, cx),
+        }
+    }
+
+    fn render_single_popup(&self, frame: &GridFrame, theme: Theme, cx: &mut Context<Self>) -> Div {
+        popup::popup_frame(theme)
+            .flex_col()
+            .w(px(350.0))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_4()
+                    .py_2()
+                    .h(px(40.0))
+                    .bg(theme.header_bg)
+                    .border_b(px(1.0))
+                    .border_color(theme.divider)
+                    .child(self.render_nav_button("prev-year", "«", 28.0, -12, theme, cx))
+                    .child(
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text)
+                            .text_lg()
+                            .child(format!("{}年", self.calendar.current_month.year()))
+                    )
+                    .child(self.render_nav_button("next-year", "»", 28.0, 12, theme, cx))
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_4()
+                    .py_2()
+                    .h(px(40.0))
+                    .bg(theme.subheader_bg)
+                    .border_b(px(1.0))
+                    .border_color(theme.divider)
+                    .child(self.render_nav_button("prev-month", "‹", 32.0, -1, theme, cx))
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child(format!("{}月", self.calendar.current_month.month()))
+                    )
+                    .child(self.render_nav_button("next-month", "›", 32.0, 1, theme, cx))
+            )
+            .child(
+                self.render_month_grid(&self.calendar, frame, theme)
+                    .on_drag_move(self.drag_edge_listener(cx))
+            )
+            .when(self.show_footer || self.require_confirm, |this| {
+                this.child(self.render_footer(theme, cx))
+            })
+    }
+
+    /// 快捷范围侧边栏加并排的两个月，共用一条导航栏
+    fn render_range_popup(&self, frame: &GridFrame, theme: Theme, cx: &mut Context<Self>) -> Div {
+        // 双月视图中隐藏相邻月份，避免同一天出现两次
+        let mut left = self.calendar.clone();
+        left.grid_options.show_adjacent_days = false;
+        let mut right = left.clone();
+        right.next_month();
+        let month_title = |month: &Calendar| {
+            div()
+                .flex_1()
+                .flex()
+                .justify_center()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(theme.text)
+                .child(format!("{}年{}月", month.current_month.year(), month.current_month.month()))
+        };
+
+        popup::popup_frame(theme)
+            .w(px(700.0))
+            .child(self.render_preset_sidebar(theme, cx))
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .px_2()
+                            .h(px(40.0))
+                            .bg(theme.header_bg)
+                            .border_b(px(1.0))
+                            .border_color(theme.divider)
+                            .child(self.render_nav_button("prev-year", "«", 28.0, -12, theme, cx))
+                            .child(self.render_nav_button("prev-month", "‹", 28.0, -1, theme, cx))
+                            .child(month_title(&left))
+                            .child(month_title(&right))
+                            .child(self.render_nav_button("next-month", "›", 28.0, 1, theme, cx))
+                            .child(self.render_nav_button("next-year", "»", 28.0, 12, theme, cx))
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .on_drag_move(self.drag_edge_listener(cx))
+                            .child(self.render_month_grid(&left, frame, theme).flex_1())
+                            .child(
+                                self.render_month_grid(&right, frame, theme)
+                                    .flex_1()
+                                    .border_l(px(1.0))
+                                    .border_color(theme.divider)
+                            )
+                    )
+                    .when(self.calendar.selection_mode == SelectionMode::Comparison, |this| {
+                        this.child(self.render_compare_bar(theme, cx))
+                    })
+                    .when(self.show_footer || self.require_confirm, |this| {
+                        this.child(self.render_footer(theme, cx))
+                    })
+            )
+    }
+
+    /// 对比方式切换；自定义对比时还可切换正在选择的是主范围还是对比范围
+    fn render_compare_bar(&self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let segment = |name: String, label: &'static str, is_active: bool, active_bg: Hsla| {
+            div()
+                .id(SharedString::from(name.clone()))
+                .debug_selector(|| format!("{}-{name}", self.id))
+                .px_2()
+                .py_1()
+                .rounded(px(4.0))
+                .text_sm()
+                .cursor_pointer()
+                .when(is_active, |this| this.bg(active_bg).text_color(theme.accent_text))
+                .when(!is_active, |this| {
+                    this.text_color(theme.text)
+                        .hover(move |style| style.bg(theme.button_hover))
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .px_3()
+            .h(px(40.0))
+            .border_t(px(1.0))
+            .border_color(theme.divider)
+            .child(div().mr_1().text_sm().text_color(theme.text_muted).child("对比"))
+            .children(CompareMode::ALL.into_iter().map(|mode| {
+                segment(
+                    format!("compare-{mode:?}"),
+                    mode.label(),
+                    self.calendar.compare_mode == mode,
+                    theme.compare_accent,
+                )
+                .on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                    picker.set_compare_mode(mode, cx);
+                }))
+            }))
+            .when(self.calendar.compare_mode == CompareMode::Custom, |this| {
+                let targets = [
+                    (CompareTarget::Primary, "主范围", theme.accent),
+                    (CompareTarget::Compare, "对比范围", theme.compare_accent),
+                ];
+                this.child(div().flex_1()).children(targets.into_iter().map(|(target, label, color)| {
+                    segment(
+                        format!("target-{target:?}"),
+                        label,
+                        self.calendar.compare_target == target,
+                        color,
+                    )
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                        picker.set_compare_target(target, cx);
+                    }))
+                }))
+            })
+    }
+
+    fn render_preset_sidebar(&self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let active = self.calendar.active_preset();
+
+        div()
+            .flex()
+            .flex_col()
+            .w(px(120.0))
+            .p_2()
+            .gap_1()
+            .bg(theme.subheader_bg)
+            .border_r(px(1.0))
+            .border_color(theme.divider)
+            .children(RangePreset::ALL.into_iter().map(|preset| {
+                let is_active = active == Some(preset);
+                div()
+                    .id(SharedString::from(format!("preset-{preset:?}")))
+                    .debug_selector(|| format!("{}-preset-{preset:?}", self.id))
+                    .px_2()
+                    .py_1()
+                    .rounded(px(4.0))
+                    .text_sm()
+                    .cursor_pointer()
+                    .when(is_active, |this| this.bg(theme.accent).text_color(theme.accent_text))
+                    .when(!is_active, |this| {
+                        this.text_color(theme.text)
+                            .hover(move |style| style.bg(theme.button_hover))
+                    })
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                        picker.apply_preset(preset, cx);
+                    }))
+                    .child(preset.label())
+            }))
+    }
+
+    /// 网格下方的「今天」「清除」，确认模式下另有「取消」「确定」
+    fn render_footer(&self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let button = |name: &'static str, label: &'static str| {
+            div()
+                .id(name)
+                .debug_selector(|| format!("{}-{name}", self.id))
+                .px_3()
+                .py_1()
+                .rounded(px(4.0))
+                .text_sm()
+                .cursor_pointer()
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .px_3()
+            .h(px(40.0))
+            .border_t(px(1.0))
+            .border_color(theme.divider)
+            .child(
+                button("today", "今天")
+                    .text_color(theme.accent)
+                    .hover(move |style| style.bg(theme.button_hover))
+                    .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                        picker.select_today(cx);
+                    }))
+            )
+            .child(
+                button("clear", "清除")
+                    .text_color(theme.text_muted)
+                    .hover(move |style| style.bg(theme.button_hover))
+                    .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                        picker.clear(cx);
+                    }))
+            )
+            .when(self.require_confirm, |this| {
+                this.child(div().flex_1())
+                    .child(
+                        button("cancel", "取消")
+                            .border(px(1.0))
+                            .border_color(theme.border)
+                            .text_color(theme.text)
+                            .hover(move |style| style.bg(theme.button_hover))
+                            .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                                picker.cancel(cx);
+                            }))
+                    )
+                    .child(
+                        button("confirm", "确定")
+                            .bg(theme.accent)
+                            .text_color(theme.accent_text)
+                            .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                                picker.confirm(cx);
+                            }))
+                    )
+            })
+    }
+
+    /// 星期标题从 `week_start` 开始，按选择器的语言显示；选周模式下左侧多一列周数
+    fn render_weekday_header(&self, week_start: Weekday, show_week_numbers: bool, theme: Theme) -> Div {
+        let week_label = match self.locale {
+            Locale::Zh => "周",
+            Locale::En => "Wk",
+        };
+        div()
+            .flex()
+            .h(px(40.0))
+            .border_b(px(1.0))
+            .border_color(theme.divider)
+            .when(show_week_numbers, |this| {
+                this.child(Self::render_week_number_cell(theme).child(week_label))
+            })
+            .children(std::iter::successors(Some(week_start), |day| Some(day.succ())).take(7).map(|weekday| {
+                div()
+                    .flex_1()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .py_2()
+                    .text_color(theme.text_muted)
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_sm()
+                    .child(self.locale.weekday_short_name(weekday))
+            }))
+    }
+
+    fn render_week_number_cell(theme: Theme) -> Div {
+        div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(px(40.0))
+            .h(px(40.0))
+            .text_xs()
+            .text_color(theme.text_muted)
+    }
+
+    /// 星期标题加日期网格
+    fn render_month_grid(&self, month: &Calendar, frame: &GridFrame, theme: Theme) -> Div {
+        let is_week_mode = month.selection_mode == SelectionMode::Week;
+
+        div()
+            .flex()
+            .flex_col()
+            .child(self.render_weekday_header(month.week_start(), is_week_mode, theme))
+            .children(self.render_month_rows(month, frame, theme))
+    }
+
+    /// 日期网格的各行，由 [`Calendar::get_month_days`] 经缓存得到
+    fn render_month_rows(&self, month: &Calendar, frame: &GridFrame, theme: Theme) -> Vec<Div> {
+        let month_days = self.grid_cache.borrow_mut().get(month);
+        let is_week_mode = month.selection_mode == SelectionMode::Week;
+
+        month_days
+            .chunks(7)
+            .map(|week| {
+                let week_number = week.iter().flatten().next().map(|date| Week::containing(*date).week());
+                div()
+                    .flex()
+                    .when(is_week_mode, |this| {
+                        this.group(WEEK_ROW_GROUP).child(
+                            Self::render_week_number_cell(theme)
+                                .children(week_number.map(|number| format!("W{number:02}")))
+                        )
+                    })
+                    .children(week.iter().map(|date_opt| {
+                        if let Some(date) = *date_opt {
+                            self.render_day_cell(month, date, frame, theme).into_any_element()
+                        } else {
+                            div()
+                                .flex_1()
+                                .h(px(40.0))
+                                .into_any_element()
+                        }
+                    }))
+            })
+            .collect()
+    }
+
+    /// 连续滚动模式中的一个月：月份标题加固定 6 行日期，隐藏相邻月份的日期
+    fn render_scroll_month(&self, month: NaiveDate, frame: &GridFrame, theme: Theme) -> Div {
+        let mut view = self.calendar.month_view(month);
+        view.grid_options.trim_trailing_rows = false;
+
+        div()
+            .flex()
+            .flex_col()
+            .h(px(SCROLL_MONTH_HEIGHT))
+            .child(Self::render_scroll_month_title(month, theme))
+            .children(self.render_month_rows(&view, frame, theme))
+    }
+
+    fn render_scroll_month_title(month: NaiveDate, theme: Theme) -> Div {
+        div()
+            .flex()
+            .items_center()
+            .px_4()
+            .h(px(40.0))
+            .bg(theme.subheader_bg)
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(theme.text)
+            .child(format!("{}年{}月", month.year(), month.month()))
+    }
+
+    /// 星期标题固定在顶部，下面是只渲染可见月份的列表，以及吸顶的月份标题与「今天」按钮
+    fn render_scroll_body(&mut self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let months = self.month_list();
+        let top = months.month(self.top_visible_month());
+        let is_week_mode = self.calendar.selection_mode == SelectionMode::Week;
+
+        let list = uniform_list(
+            "months",
+            months.len(),
+            cx.processor(move |picker, range: Range<usize>, _, cx| {
+                let frame = picker.grid_frame(cx);
+                range
+                    .map(|ix| picker.render_scroll_month(months.month(ix), &frame, theme))
+                    .collect::<Vec<_>>()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full();
+
+        popup::popup_frame(theme)
+            .flex_col()
+            .w(px(350.0))
+            .child(self.render_weekday_header(self.calendar.week_start(), is_week_mode, theme))
+            .child(
+                div()
+                    .relative()
+                    .h(px(SCROLL_VIEW_HEIGHT))
+                    .on_drag_move(self.drag_edge_listener(cx))
+                    .child(list)
+                    .child(
+                        Self::render_scroll_month_title(top, theme)
+                            .debug_selector(|| format!("{}-sticky-month", self.id))
+                            .absolute()
+                            .top_0()
+                            .left_0()
+                            .right_0()
+                            .justify_between()
+                            .border_b(px(1.0))
+                            .border_color(theme.divider)
+                            .child(
+                                div()
+                                    .id("jump-today")
+                                    .debug_selector(|| format!("{}-jump-today", self.id))
+                                    .px_2()
+                                    .py_1()
+                                    .rounded(px(4.0))
+                                    .text_sm()
+                                    .font_weight(FontWeight::NORMAL)
+                                    .text_color(theme.accent)
+                                    .cursor_pointer()
+                                    .hover(move |style| style.bg(theme.button_hover))
+                                    .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                                        picker.jump_to_today(cx);
+                                    }))
+                                    .child("今天")
+                            )
+                    )
+            )
+            .when(self.show_footer || self.require_confirm, |this| {
+                this.child(self.render_footer(theme, cx))
+            })
+    }
+}
+
+impl Render for DatePicker {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        watch_appearance(window, cx);
+        let theme = *cx.theme();
+        watch_clock(self.calendar.clock, cx);
+
+        let root = div()
+            .track_focus(&self.focus_handle)
+            .key_context(KEY_CONTEXT)
+            .on_action(cx.listener(|picker, _: &MoveLeft, _, cx| picker.move_focus(-1, cx)))
+            .on_action(cx.listener(|picker, _: &MoveRight, _, cx| picker.move_focus(1, cx)))
+            .on_action(cx.listener(|picker, _: &MoveUp, _, cx| picker.move_focus(-7, cx)))
+            .on_action(cx.listener(|picker, _: &MoveDown, _, cx| picker.move_focus(7, cx)))
+            .on_action(cx.listener(|picker, _: &PreviousMonth, _, cx| picker.move_focus_months(-1, cx)))
+            .on_action(cx.listener(|picker, _: &NextMonth, _, cx| picker.move_focus_months(1, cx)))
+            .on_action(cx.listener(|picker, _: &PreviousYear, _, cx| picker.move_focus_months(-12, cx)))
+            .on_action(cx.listener(|picker, _: &NextYear, _, cx| picker.move_focus_months(12, cx)))
+            .on_action(cx.listener(|picker, _: &SelectFocused, _, cx| picker.select_focused(cx)))
+            .on_action(cx.listener(|picker, _: &Dismiss, _, cx| picker.dismiss(cx)))
+            .on_action(cx.listener(|picker, _: &CopyValue, _, cx| picker.copy(cx)))
+            .on_action(cx.listener(|picker, _: &PasteValue, _, cx| picker.paste_from_clipboard(cx)))
+            .relative()
+            .when_some(self.label.clone(), |this, label| this.child(popup::label(label, theme)));
+
+        let content = match self.display_mode {
+            DisplayMode::Inline => {
+                let is_focused = self.focus_handle.is_focused(window);
+                let popup = self.render_popup(theme, cx);
+                root.child(
+                    self.cancel_drag_outside_days(popup, cx)
+                        .when(is_focused, |this| this.border_color(theme.accent))
+                        .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, window, _| {
+                            window.focus(&picker.focus_handle);
+                        }))
+                )
+            }
+            DisplayMode::Scroll => {
+                let is_focused = self.focus_handle.is_focused(window);
+                let body = self.render_scroll_body(theme, cx);
+                root.child(
+                    self.cancel_drag_outside_days(body, cx)
+                        .when(is_focused, |this| this.border_color(theme.accent))
+                        .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, window, _| {
+                            window.focus(&picker.focus_handle);
+                        }))
+                )
+            }
+            DisplayMode::Popup => root
+                .w(px(250.0))
+                .child(self.render_trigger(theme, cx))
+                .when_some(self.caption(), |this, caption| {
+                    this.child(
+                        div()
+                            .mt_1()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .child(caption)
+                    )
+                })
+                .when(self.is_open, |this| {
+                    let popup = self.render_popup(theme, cx);
+                    let popup = self.cancel_drag_outside_days(popup, cx).shadow_lg();
+                    let mask = popup::mask(&self.id, "mask", window, cx.listener(|picker, _, _, cx| {
+                        picker.close(cx);
+                    }));
+                    this.child(popup::anchor(popup, self.show_above)).child(mask)
+                }),
+        };
+        content.when_some(self.context_menu, |this, (date, position)| {
+            this.child(self.render_context_menu(date, position, theme, window, cx))
+        })
+    }
+}
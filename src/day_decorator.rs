@@ -0,0 +1,78 @@
+use chrono::NaiveDate;
+use gpui::*;
+
+use crate::theme::Theme;
+
+/// 日期格的状态，传给 [`DayDecorator`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayCellState {
+    pub is_today: bool,
+    /// 选中的日期或范围端点
+    pub is_selected: bool,
+    /// 属于网格所显示的月份，相邻月份的日期为 `false`
+    pub is_current_month: bool,
+    /// 不满足规则、不能选择
+    pub is_disabled: bool,
+}
+
+/// 一个装饰器对日期格的修改；`None` 的颜色沿用默认样式或低优先级装饰器给出的颜色
+#[derive(Default)]
+pub struct DayDecoration {
+    pub background: Option<Hsla>,
+    pub text_color: Option<Hsla>,
+    /// 以小号字排在日期数字下方的元素，例如价格、余量；日期方块为 relative，也可以用 absolute 定位成角标
+    pub children: Vec<AnyElement>,
+}
+
+impl DayDecoration {
+    pub fn background(mut self, color: impl Into<Hsla>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    pub fn text_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.text_color = Some(color.into());
+        self
+    }
+
+    pub fn child(mut self, child: impl IntoElement) -> Self {
+        self.children.push(child.into_any_element());
+        self
+    }
+
+    /// 叠加一个更高优先级的装饰：颜色以 `other` 为准，附加元素排在后面
+    pub fn merge(mut self, other: DayDecoration) -> Self {
+        self.background = other.background.or(self.background);
+        self.text_color = other.text_color.or(self.text_color);
+        self.children.extend(other.children);
+        self
+    }
+}
+
+/// 在日期格中绘制价格、余量、角标或自定义颜色，不必改动 [`DatePicker`](crate::DatePicker) 的渲染。
+///
+/// 多个装饰器按 [`priority`](Self::priority) 从低到高依次叠加，见 [`DayDecoration::merge`]：
+///
+/// ```ignore
+/// struct Prices(HashMap<NaiveDate, u32>);
+///
+/// impl DayDecorator for Prices {
+///     fn decorate(&self, date: NaiveDate, state: DayCellState, theme: &Theme) -> DayDecoration {
+///         match self.0.get(&date) {
+///             Some(price) if !state.is_disabled => DayDecoration::default().child(format!("¥{price}")),
+///             _ => DayDecoration::default(),
+///         }
+///     }
+/// }
+///
+/// DatePicker::new(cx).decorator(Prices(prices))
+/// ```
+pub trait DayDecorator {
+    /// 优先级相同的装饰器按添加顺序叠加
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// 每次渲染对每个日期格调用一次
+    fn decorate(&self, date: NaiveDate, state: DayCellState, theme: &Theme) -> DayDecoration;
+}
@@ -0,0 +1,106 @@
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use gpui::*;
+
+use crate::locale::Locale;
+use crate::theme::ActiveTheme;
+
+/// 日期悬停提示中的一项：节假日、日程或自定义备注
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayItem {
+    pub title: SharedString,
+    /// 起止时间，`None` 表示全天
+    pub time: Option<(NaiveTime, NaiveTime)>,
+    /// 所属日历的颜色，显示为标题前的色块
+    pub color: Hsla,
+}
+
+impl DayItem {
+    /// 全天事项
+    pub fn new(title: impl Into<SharedString>, color: impl Into<Hsla>) -> Self {
+        Self {
+            title: title.into(),
+            time: None,
+            color: color.into(),
+        }
+    }
+
+    pub fn time(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        self.time = Some((start, end));
+        self
+    }
+
+    /// 例如「09:30–10:00」；起止相同时只显示一个时间，全天事项显示「全天」或 `All day`
+    pub fn time_label(&self, locale: Locale) -> String {
+        match self.time {
+            None => match locale {
+                Locale::Zh => "全天".to_string(),
+                Locale::En => "All day".to_string(),
+            },
+            Some((start, end)) if start == end => start.format("%H:%M").to_string(),
+            Some((start, end)) => format!("{}–{}", start.format("%H:%M"), end.format("%H:%M")),
+        }
+    }
+}
+
+/// 日期格的悬停提示：日期与星期，下面逐行列出各项
+pub(crate) struct DayTooltip {
+    selector: String,
+    date: NaiveDate,
+    items: Vec<DayItem>,
+    locale: Locale,
+}
+
+impl Render for DayTooltip {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        let selector = self.selector.clone();
+
+        div()
+            .debug_selector(move || selector)
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .border(px(1.0))
+            .border_color(theme.border)
+            .rounded(px(4.0))
+            .bg(theme.surface)
+            .shadow_md()
+            .text_xs()
+            .text_color(theme.text)
+            .child(
+                div()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(format!("{} {}", self.date, self.locale.weekday_name(self.date.weekday())))
+            )
+            .children(self.items.iter().map(|item| {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().size(px(8.0)).rounded(px(2.0)).bg(item.color))
+                    .child(div().text_color(theme.text_muted).child(item.time_label(self.locale)))
+                    .child(item.title.clone())
+            }))
+    }
+}
+
+/// 用于 `.tooltip(..)`；提示的调试选择器为 `"{id}-tooltip"`
+pub(crate) fn day_tooltip(
+    id: &SharedString,
+    date: NaiveDate,
+    items: Vec<DayItem>,
+    locale: Locale,
+) -> impl Fn(&mut Window, &mut App) -> AnyView + 'static {
+    let selector = format!("{id}-tooltip");
+    move |_, cx| {
+        cx.new(|_| DayTooltip {
+            selector: selector.clone(),
+            date,
+            items: items.clone(),
+            locale,
+        })
+        .into()
+    }
+}
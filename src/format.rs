@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::period::Week;
+use crate::range::DateRange;
+use crate::selection::{DateValue, SelectionMode};
+
+/// 日期的显示与解析格式，使用 chrono 的 strftime 语法，例如 `"%Y-%m-%d"`。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateFormat {
+    pattern: Cow<'static, str>,
+}
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        Self::ISO
+    }
+}
+
+impl DateFormat {
+    /// ISO 8601 日期格式 `2025-03-01`
+    pub const ISO: Self = Self {
+        pattern: Cow::Borrowed("%Y-%m-%d"),
+    };
+
+    pub fn new(pattern: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            pattern: pattern.into(),
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn format(&self, date: NaiveDate) -> String {
+        date.format(&self.pattern).to_string()
+    }
+
+    /// 「起 ~ 止」
+    pub fn format_range(&self, range: DateRange) -> String {
+        format!("{} ~ {}", self.format(range.start), self.format(range.end))
+    }
+
+    /// 选择器输入框中的文字：范围为「起 ~ 止」，对比为「主范围 对比 对比范围」，周为 `2025-W09`
+    pub fn format_value(&self, value: &DateValue) -> String {
+        match value {
+            DateValue::Date(date) => self.format(*date),
+            DateValue::Range(range) => self.format_range(*range),
+            DateValue::Comparison { primary, compare } => {
+                format!("{} 对比 {}", self.format_range(*primary), self.format_range(*compare))
+            }
+            DateValue::Week(week) => week.to_string(),
+        }
+    }
+
+    /// 按选择模式解析文本，其中的日期与 [`parse`](Self::parse) 相同。
+    ///
+    /// 范围接受「起 ~ 止」或 ISO 8601 区间 `2025-03-01/2025-03-07`，只有一个日期时为当天；
+    /// 对比模式接受 [`format_value`](Self::format_value) 的「主范围 对比 对比范围」或
+    /// [`DateValue::to_iso_string`] 的 `起/止,起/止`，只有一段范围时为主范围；
+    /// 选周模式接受 `2025-W09` 或周内任意一天。
+    pub fn parse_value(&self, text: &str, mode: SelectionMode) -> Result<DateValue, ParseDateError> {
+        match mode {
+            SelectionMode::Single => self.parse(text).map(DateValue::Date),
+            SelectionMode::Week => {
+                let week = NaiveDate::parse_from_str(&format!("{}-1", text.trim()), "%G-W%V-%u");
+                match week {
+                    Ok(monday) => Ok(DateValue::Week(Week::containing(monday))),
+                    Err(_) => self.parse(text).map(|date| DateValue::Week(Week::containing(date))),
+                }
+            }
+            SelectionMode::Range => self.parse_range(text).map(DateValue::Range),
+            SelectionMode::Comparison => {
+                // 「,」也可能出现在日期格式中，两边都是 ISO 区间时才按两段范围处理
+                let ranges = match text.split_once("对比") {
+                    Some((primary, compare)) => Some((self.parse_range(primary)?, self.parse_range(compare)?)),
+                    None => text.split_once(',').and_then(|(primary, compare)| {
+                        Self::parse_iso_range(primary).zip(Self::parse_iso_range(compare))
+                    }),
+                };
+                match ranges {
+                    Some((primary, compare)) => Ok(DateValue::Comparison { primary, compare }),
+                    None => self.parse_range(text).map(DateValue::Range),
+                }
+            }
+        }
+    }
+
+    fn parse_range(&self, text: &str) -> Result<DateRange, ParseDateError> {
+        if let Some((start, end)) = text.split_once('~') {
+            return Ok(DateRange::new(self.parse(start)?, self.parse(end)?));
+        }
+        // 「/」也可能是日期格式中的分隔符，两边都是 ISO 日期时才按区间处理
+        match Self::parse_iso_range(text) {
+            Some(range) => Ok(range),
+            None => self.parse(text).map(DateRange::single),
+        }
+    }
+
+    fn parse_iso_range(text: &str) -> Option<DateRange> {
+        let (start, end) = text.split_once('/')?;
+        let start = NaiveDate::parse_from_str(start.trim(), Self::ISO.pattern()).ok()?;
+        let end = NaiveDate::parse_from_str(end.trim(), Self::ISO.pattern()).ok()?;
+        Some(DateRange::new(start, end))
+    }
+
+    /// 按本格式解析文本，失败时再尝试 ISO 8601，首尾空白会被忽略
+    pub fn parse(&self, text: &str) -> Result<NaiveDate, ParseDateError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ParseDateError::Empty);
+        }
+        NaiveDate::parse_from_str(text, &self.pattern)
+            .or_else(|_| NaiveDate::parse_from_str(text, Self::ISO.pattern()))
+            .map_err(|_| ParseDateError::Invalid(text.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseDateError {
+    Empty,
+    /// 文本不符合格式，附带原始文本
+    Invalid(String),
+    /// 日期有效但不满足 [`DateRules`](crate::DateRules)，附带原始文本
+    NotAllowed(String),
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDateError::Empty => write!(f, "日期为空"),
+            ParseDateError::Invalid(text) => write!(f, "无法解析日期：{text}"),
+            ParseDateError::NotAllowed(text) => write!(f, "日期不在可选范围内：{text}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDateError {}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::calendar::add_days;
+use crate::format::{DateFormat, ParseDateError};
+use crate::locale::Locale;
+
+/// 热力图显示的周数，与 GitHub 贡献图一样覆盖最近一年
+pub const HEATMAP_WEEKS: usize = 53;
+
+/// 热力图的数据：每天一个数值，同一天的多条记录相加。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeatmapData {
+    values: BTreeMap<NaiveDate, f64>,
+}
+
+impl FromIterator<(NaiveDate, f64)> for HeatmapData {
+    fn from_iter<I: IntoIterator<Item = (NaiveDate, f64)>>(iter: I) -> Self {
+        let mut data = Self::default();
+        for (date, value) in iter {
+            data.add(date, value);
+        }
+        data
+    }
+}
+
+impl HeatmapData {
+    pub fn add(&mut self, date: NaiveDate, value: f64) {
+        *self.values.entry(date).or_default() += value;
+    }
+
+    pub fn get(&self, date: &NaiveDate) -> Option<f64> {
+        self.values.get(date).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, f64)> + '_ {
+        self.values.iter().map(|(date, value)| (*date, *value))
+    }
+
+    /// 每行 `日期,数值` 的 CSV；空行和 `#` 开头的行被忽略，第一个有内容的行不是日期时视为表头
+    pub fn from_csv(text: &str, format: &DateFormat) -> Result<Self, ParseHeatmapError> {
+        let mut data = Self::default();
+        let mut is_first_row = true;
+        for (idx, line) in text.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let may_be_header = std::mem::replace(&mut is_first_row, false);
+            let (date, value) = line.split_once(',').unwrap_or((line, ""));
+            let date = match format.parse(unquote(date)) {
+                Ok(date) => date,
+                Err(_) if may_be_header => continue,
+                Err(error) => return Err(ParseHeatmapError::Date { line: line_number, error }),
+            };
+            let value = unquote(value);
+            let value = value.parse().map_err(|_| ParseHeatmapError::Value {
+                line: line_number,
+                text: value.to_string(),
+            })?;
+            data.add(date, value);
+        }
+        Ok(data)
+    }
+
+    /// `{"2025-03-14": 3}` 形式的对象，或 `[{"date": "2025-03-14", "value": 3}]` 形式的数组
+    pub fn from_json(text: &str, format: &DateFormat) -> Result<Self, ParseHeatmapError> {
+        let json: serde_json::Value =
+            serde_json::from_str(text).map_err(|error| ParseHeatmapError::Json(error.to_string()))?;
+        let entries: Vec<(&str, &serde_json::Value)> = match &json {
+            serde_json::Value::Object(map) => map.iter().map(|(date, value)| (date.as_str(), value)).collect(),
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(|item| {
+                    let date = item.get("date").and_then(|date| date.as_str()).unwrap_or_default();
+                    (date, item.get("value").unwrap_or(&serde_json::Value::Null))
+                })
+                .collect(),
+            _ => return Err(ParseHeatmapError::Json("应为对象或数组".to_string())),
+        };
+
+        let mut data = Self::default();
+        for (idx, (date, value)) in entries.into_iter().enumerate() {
+            let line = idx + 1;
+            let date = format.parse(date).map_err(|error| ParseHeatmapError::Date { line, error })?;
+            let value = value.as_f64().ok_or_else(|| ParseHeatmapError::Value {
+                line,
+                text: value.to_string(),
+            })?;
+            data.add(date, value);
+        }
+        Ok(data)
+    }
+}
+
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseHeatmapError {
+    /// JSON 语法错误，或顶层不是对象或数组
+    Json(String),
+    /// 第 `line` 条记录的日期无效；CSV 按行号计数，JSON 按数组位置或对象的键序计数
+    Date { line: usize, error: ParseDateError },
+    /// 第 `line` 条记录的数值无效，附带原始文本
+    Value { line: usize, text: String },
+}
+
+impl fmt::Display for ParseHeatmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseHeatmapError::Json(message) => write!(f, "JSON 无效：{message}"),
+            ParseHeatmapError::Date { line, error } => write!(f, "第 {line} 条：{error}"),
+            ParseHeatmapError::Value { line, text } => write!(f, "第 {line} 条：数值无效：{text}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseHeatmapError {}
+
+/// 数值到颜色深浅的分级：第 `n` 个阈值是第 `n + 1` 级的下限，低于第一个阈值或没有数据为第 0 级。
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeatmapScale {
+    thresholds: Vec<f64>,
+}
+
+impl Default for HeatmapScale {
+    fn default() -> Self {
+        Self::new([1.0, 3.0, 6.0, 10.0])
+    }
+}
+
+impl HeatmapScale {
+    /// 阈值会被排序，顺序无关
+    pub fn new(thresholds: impl IntoIterator<Item = f64>) -> Self {
+        let mut thresholds: Vec<f64> = thresholds.into_iter().filter(|value| !value.is_nan()).collect();
+        thresholds.sort_by(f64::total_cmp);
+        Self { thresholds }
+    }
+
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    /// 最高的级别，也就是阈值的个数
+    pub fn max_level(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    pub fn level(&self, value: Option<f64>) -> usize {
+        value.map_or(0, |value| self.thresholds.partition_point(|threshold| *threshold <= value))
+    }
+}
+
+/// 以 `end` 所在的周为最后一列、向前共 [`HEATMAP_WEEKS`] 列的网格，每列一周、从 `week_start` 开始；
+/// `end` 之后的格子为 `None`。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeatmapLayout {
+    pub weeks: Vec<[Option<NaiveDate>; 7]>,
+}
+
+impl HeatmapLayout {
+    pub fn new(end: NaiveDate, week_start: Weekday) -> Self {
+        let last_week = end.weekday().days_since(week_start) as i64;
+        let first = -(last_week + 7 * (HEATMAP_WEEKS as i64 - 1));
+        let weeks = (0..HEATMAP_WEEKS as i64)
+            .map(|week| {
+                std::array::from_fn(|day| {
+                    let offset = first + week * 7 + day as i64;
+                    if offset > 0 { None } else { add_days(end, offset) }
+                })
+            })
+            .collect();
+        Self { weeks }
+    }
+
+    /// 含有某月 1 号的列及该月，用于在列上方标出月份
+    pub fn month_starts(&self) -> Vec<(usize, NaiveDate)> {
+        self.weeks
+            .iter()
+            .enumerate()
+            .filter_map(|(column, week)| {
+                week.iter().flatten().find(|date| date.day() == 1).map(|date| (column, *date))
+            })
+            .collect()
+    }
+}
+
+/// 格子的悬停说明，例如「2025-03-14 周五：3」、`Friday, 2025-03-14: 3`
+pub fn heatmap_tooltip(date: NaiveDate, value: Option<f64>, locale: Locale) -> String {
+    let weekday = locale.weekday_name(date.weekday());
+    match (locale, value) {
+        (Locale::Zh, Some(value)) => format!("{date} {weekday}：{value}"),
+        (Locale::Zh, None) => format!("{date} {weekday}：无数据"),
+        (Locale::En, Some(value)) => format!("{weekday}, {date}: {value}"),
+        (Locale::En, None) => format!("{weekday}, {date}: no data"),
+    }
+}
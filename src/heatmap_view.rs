@@ -0,0 +1,282 @@
+use std::rc::Rc;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::calendar::{Calendar, GridOptions};
+use crate::clock::Clock;
+use crate::heatmap::{HeatmapData, HeatmapLayout, HeatmapScale, heatmap_tooltip};
+use crate::locale::Locale;
+use crate::popup;
+use crate::rollover::watch_clock;
+use crate::rules::DateRules;
+use crate::selection::{DateValue, SelectionMode};
+use crate::theme::{ActiveTheme, Theme, watch_appearance};
+
+const CELL_SIZE: f32 = 12.0;
+const CELL_GAP: f32 = 3.0;
+/// 左侧星期标签的宽度
+const LABEL_WIDTH: f32 = 28.0;
+
+/// GitHub 风格的贡献热力图：最近 53 周每天一个方格，颜色深浅表示数值大小。
+///
+/// 每周的起始日、规则与选择方式沿用 [`Calendar`]，点击方格与在 [`DatePicker`](crate::DatePicker)
+/// 中点击日期的效果相同：
+///
+/// ```ignore
+/// let data = HeatmapData::from_csv(&text, &DateFormat::ISO)?;
+/// let heatmap = cx.new(|_| Heatmap::new().data(data).thresholds([1.0, 5.0, 10.0]));
+/// ```
+pub struct Heatmap {
+    id: SharedString,
+    calendar: Calendar,
+    data: HeatmapData,
+    scale: HeatmapScale,
+    locale: Locale,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeatmapEvent {
+    /// 选中的日期或范围发生变化
+    Changed(Option<DateValue>),
+}
+
+impl EventEmitter<HeatmapEvent> for Heatmap {}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heatmap {
+    pub fn new() -> Self {
+        Self {
+            id: "heatmap".into(),
+            calendar: Calendar {
+                selected_date: None,
+                // 热力图跨越一年，不区分当月与相邻月份
+                grid_options: GridOptions {
+                    select_adjacent_days: true,
+                    ..GridOptions::default()
+                },
+                ..Calendar::new()
+            },
+            data: HeatmapData::default(),
+            scale: HeatmapScale::default(),
+            locale: Locale::default(),
+        }
+    }
+
+    /// 组件标识，用作各方格调试选择器的前缀，例如 `"{id}-day-2025-03-14"`
+    pub fn id(mut self, id: impl Into<SharedString>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn data(mut self, data: HeatmapData) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// 各级颜色的下限，见 [`HeatmapScale`]
+    pub fn thresholds(mut self, thresholds: impl IntoIterator<Item = f64>) -> Self {
+        self.scale = HeatmapScale::new(thresholds);
+        self
+    }
+
+    /// 星期、月份标签与悬停说明使用的语言
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 每列的第一天；选周模式下固定为周一
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.calendar.grid_options.week_start = week_start;
+        self
+    }
+
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.calendar.selection_mode = mode;
+        self
+    }
+
+    /// 不满足规则的方格变淡且不能选中
+    pub fn rules(mut self, rules: DateRules) -> Self {
+        self.calendar.rules = rules;
+        self
+    }
+
+    /// 「今天」的来源，决定最后一列
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.calendar.clock = clock;
+        self
+    }
+
+    pub fn set_data(&mut self, data: HeatmapData, cx: &mut Context<Self>) {
+        self.data = data;
+        cx.notify();
+    }
+
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    pub fn value(&self) -> Option<DateValue> {
+        self.calendar.value()
+    }
+
+    /// 按选择模式处理一次点击，与日期选择器相同；不满足规则的日期被忽略
+    pub fn select(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        if !self.calendar.is_selectable(&date) {
+            return;
+        }
+        let previous = self.calendar.value();
+        self.calendar.pick(date);
+        let value = self.calendar.value();
+        if value != previous {
+            cx.emit(HeatmapEvent::Changed(value));
+        }
+        cx.notify();
+    }
+
+    fn level_color(&self, level: usize, theme: Theme) -> Hsla {
+        if level == 0 {
+            theme.heat_empty
+        } else {
+            theme.heat_full.opacity(level as f32 / self.scale.max_level() as f32)
+        }
+    }
+
+    fn render_cell(
+        &self,
+        date: NaiveDate,
+        today: NaiveDate,
+        pick: &Rc<dyn Fn(&NaiveDate, &mut Window, &mut App)>,
+        theme: Theme,
+    ) -> Stateful<Div> {
+        let value = self.data.get(&date);
+        let is_marked = self.calendar.is_selected(&date) || self.calendar.is_in_range(&date);
+        let is_allowed = self.calendar.is_selectable(&date);
+        let locale = self.locale;
+
+        div()
+            .id(SharedString::from(format!("cell-{date}")))
+            .debug_selector(|| format!("{}-day-{date}", self.id))
+            .size(px(CELL_SIZE))
+            .rounded(px(2.0))
+            .bg(self.level_color(self.scale.level(value), theme))
+            .when(is_marked, |this| this.border_1().border_color(theme.accent))
+            .when(date == today && !is_marked, |this| this.border_1().border_color(theme.today_text))
+            .when(!is_allowed, |this| this.opacity(0.4))
+            .when(is_allowed, |this| this.cursor_pointer())
+            .tooltip(popup::text_tooltip(move || heatmap_tooltip(date, value, locale)))
+            .on_mouse_down(MouseButton::Left, {
+                let pick = pick.clone();
+                move |_, window, cx| pick(&date, window, cx)
+            })
+    }
+
+    fn render_month_labels(&self, layout: &HeatmapLayout, theme: Theme) -> Div {
+        let month_starts = layout.month_starts();
+
+        div()
+            .flex()
+            .pl(px(LABEL_WIDTH + CELL_GAP))
+            .h(px(16.0))
+            .text_xs()
+            .text_color(theme.text_muted)
+            .children((0..layout.weeks.len()).map(|column| {
+                let label = month_starts
+                    .iter()
+                    .find(|(start, _)| *start == column)
+                    .map(|(_, month)| self.locale.month_short_name(month.month()));
+                div()
+                    .flex_none()
+                    .w(px(CELL_SIZE + CELL_GAP))
+                    .whitespace_nowrap()
+                    .children(label)
+            }))
+    }
+
+    /// 每隔一行标出星期，避免文字挤在一起
+    fn render_weekday_labels(&self, theme: Theme) -> Div {
+        let week_start = self.calendar.week_start();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(CELL_GAP))
+            .w(px(LABEL_WIDTH))
+            .text_xs()
+            .text_color(theme.text_muted)
+            .children((0..7).map(|row| {
+                let weekday = (0..row).fold(week_start, |day, _| day.succ());
+                div()
+                    .flex()
+                    .items_center()
+                    .h(px(CELL_SIZE))
+                    .when(row % 2 == 1, |this| this.child(self.locale.weekday_short_name(weekday)))
+            }))
+    }
+
+    fn render_legend(&self, theme: Theme) -> Div {
+        let (less, more) = match self.locale {
+            Locale::Zh => ("少", "多"),
+            Locale::En => ("Less", "More"),
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .justify_end()
+            .gap(px(CELL_GAP))
+            .text_xs()
+            .text_color(theme.text_muted)
+            .child(div().mr_1().child(less))
+            .children((0..=self.scale.max_level()).map(|level| {
+                div()
+                    .size(px(CELL_SIZE))
+                    .rounded(px(2.0))
+                    .bg(self.level_color(level, theme))
+            }))
+            .child(div().ml_1().child(more))
+    }
+}
+
+impl Render for Heatmap {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        watch_appearance(window, cx);
+        let theme = *cx.theme();
+        watch_clock(self.calendar.clock, cx);
+        let today = self.calendar.today();
+        let layout = HeatmapLayout::new(today, self.calendar.week_start());
+        let pick: Rc<dyn Fn(&NaiveDate, &mut Window, &mut App)> =
+            Rc::new(cx.listener(|heatmap, date: &NaiveDate, _, cx| heatmap.select(*date, cx)));
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(self.render_month_labels(&layout, theme))
+            .child(
+                div()
+                    .flex()
+                    .gap(px(CELL_GAP))
+                    .child(self.render_weekday_labels(theme))
+                    .children(layout.weeks.iter().map(|week| {
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(px(CELL_GAP))
+                            .children(week.iter().map(|date| match *date {
+                                Some(date) => self.render_cell(date, today, &pick, theme).into_any_element(),
+                                None => div().size(px(CELL_SIZE)).into_any_element(),
+                            }))
+                    }))
+            )
+            .child(self.render_legend(theme))
+    }
+}
@@ -0,0 +1,83 @@
+//! 基于 GPUI 的日历与日期选择组件。
+//!
+//! - [`Calendar`]：月份导航、6×7 月历网格计算，不依赖 GPUI。
+//! - [`WorkingDays`]：周末与节假日，[`Calendar`] 据此加减工作日、统计范围内的工作日数。
+//! - [`DateRange`]、[`RangePreset`]：日期范围与「最近 7 天」等快捷范围，不依赖 GPUI。
+//! - [`DateRules`]：最早/最晚日期与禁用日期，所有选择器共用。
+//! - [`Clock::Zone`]：按 IANA 时区计算「今天」；[`start_of_day`]、[`date_in_zone`] 等做跨时区换算。
+//! - [`DateFormat`]：日期的格式化与解析，不依赖 GPUI。
+//! - [`DatePicker`]：带弹出日历的日期选择器，需要启用 `gpui` feature（默认启用）。
+//! - [`MonthPicker`]、[`QuarterPicker`]、[`YearPicker`]：按月、季度、年选择，同样需要 `gpui` feature；
+//!   核心状态 [`PeriodCalendar`] 不依赖 GPUI。
+//! - [`HeatmapData`]：从 CSV 或 JSON 读入的每日数值，[`Heatmap`] 据此绘制最近 53 周的贡献热力图。
+//! - [`YearView`]：一年 12 个月的小月历，标出事件与节假日，点击后在 [`DatePicker`] 中打开该月。
+//!
+//! 只需要日期逻辑时可以关闭默认 feature：
+//!
+//! ```toml
+//! gpui-demo = { version = "0.1", default-features = false }
+//! ```
+
+mod business;
+mod calendar;
+mod clock;
+mod format;
+mod heatmap;
+mod locale;
+mod period;
+mod range;
+mod relative;
+mod rules;
+mod selection;
+mod zone;
+
+#[cfg(feature = "gpui")]
+mod date_picker;
+#[cfg(feature = "gpui")]
+mod day_decorator;
+#[cfg(feature = "gpui")]
+mod day_tooltip;
+#[cfg(feature = "gpui")]
+mod heatmap_view;
+#[cfg(feature = "gpui")]
+mod period_picker;
+#[cfg(feature = "gpui")]
+mod popup;
+#[cfg(feature = "gpui")]
+mod rollover;
+#[cfg(feature = "gpui")]
+mod theme;
+#[cfg(feature = "gpui")]
+mod year_view;
+
+pub use business::WorkingDays;
+pub use calendar::{Calendar, GridCache, GridOptions, MonthList, add_days, days_in_month, first_of_month, shift_month};
+pub use clock::Clock;
+pub use format::{DateFormat, ParseDateError};
+pub use heatmap::{HEATMAP_WEEKS, HeatmapData, HeatmapLayout, HeatmapScale, ParseHeatmapError, heatmap_tooltip};
+pub use locale::Locale;
+pub use period::{Period, PeriodCalendar, Quarter, Week, Year, YearMonth};
+pub use range::{CompareMode, DateRange, RangePreset};
+pub use relative::{RelativeStyle, describe_relative, describe_relative_range};
+pub use rules::DateRules;
+pub use selection::{CompareTarget, DateValue, SelectionMode};
+pub use zone::{convert_start_of_day, date_in_zone, range_instants, start_of_day, zone_abbreviation};
+
+pub use chrono_tz::Tz;
+
+#[cfg(feature = "gpui")]
+pub use date_picker::{DatePicker, DatePickerEvent, DisplayMode, init};
+#[cfg(feature = "gpui")]
+pub use day_decorator::{DayCellState, DayDecoration, DayDecorator};
+#[cfg(feature = "gpui")]
+pub use day_tooltip::DayItem;
+#[cfg(feature = "gpui")]
+pub use heatmap_view::{Heatmap, HeatmapEvent};
+#[cfg(feature = "gpui")]
+pub use period_picker::{MonthPicker, PeriodPicker, PeriodPickerEvent, QuarterPicker, YearPicker};
+#[cfg(feature = "gpui")]
+pub use rollover::{DayRollover, watch_clock};
+#[cfg(feature = "gpui")]
+pub use theme::{ActiveTheme, Theme, ThemeMode, ThemeState, set_theme_mode, watch_appearance};
+#[cfg(feature = "gpui")]
+pub use year_view::{YearColumns, YearView};
@@ -0,0 +1,48 @@
+use chrono::Weekday;
+
+/// 选择器说明文字等使用的语言
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Locale {
+    /// 星期的完整名称，例如「周二」、`Tuesday`
+    pub fn weekday_name(self, weekday: Weekday) -> &'static str {
+        let idx = weekday.num_days_from_monday() as usize;
+        match self {
+            Locale::Zh => ["周一", "周二", "周三", "周四", "周五", "周六", "周日"][idx],
+            Locale::En => [
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+                "Sunday",
+            ][idx],
+        }
+    }
+
+    /// 星期的简称，例如「二」、`Tue`
+    pub fn weekday_short_name(self, weekday: Weekday) -> &'static str {
+        let idx = weekday.num_days_from_monday() as usize;
+        match self {
+            Locale::Zh => ["一", "二", "三", "四", "五", "六", "日"][idx],
+            Locale::En => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"][idx],
+        }
+    }
+
+    /// 月份的简称，`month` 从 1 开始，例如「3月」、`Mar`
+    pub fn month_short_name(self, month: u32) -> String {
+        match self {
+            Locale::Zh => format!("{month}月"),
+            Locale::En => [
+                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+            ][(month as usize + 11) % 12]
+                .to_string(),
+        }
+    }
+}
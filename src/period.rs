@@ -0,0 +1,366 @@
+use std::fmt;
+
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+
+use crate::calendar::{Calendar, add_days, first_of_month};
+use crate::clock::Clock;
+use crate::range::DateRange;
+use crate::rules::DateRules;
+
+/// 月份、季度、年份等比「天」更粗的选择单位。
+///
+/// 每种周期决定自己一页网格的内容与列数，翻页借用 [`Calendar`] 的导航。
+pub trait Period: Copy + Ord + fmt::Debug + fmt::Display + 'static {
+    /// 一页网格覆盖的年数，翻页时按此平移
+    const PAGE_YEARS: i32;
+    /// 网格的列数
+    const COLUMNS: usize;
+    /// 用于占位文字等处的名称，例如「月份」
+    const NAME: &'static str;
+
+    /// 日期所在的周期
+    fn containing(date: NaiveDate) -> Self;
+
+    fn first_day(self) -> NaiveDate;
+
+    fn last_day(self) -> NaiveDate;
+
+    /// 网格格子中的简短文字
+    fn cell_label(self) -> String;
+
+    /// `month` 所在那一页的全部格子
+    fn page(month: NaiveDate) -> Vec<Self>;
+
+    /// `month` 所在那一页的标题
+    fn page_title(month: NaiveDate) -> String;
+
+    /// 格子属于 `month` 所在的页，而不是为补齐网格显示的相邻页
+    fn is_on_page(self, month: NaiveDate) -> bool {
+        let _ = month;
+        true
+    }
+
+    fn range(self) -> DateRange {
+        DateRange::new(self.first_day(), self.last_day())
+    }
+
+    fn contains(self, date: &NaiveDate) -> bool {
+        self.range().contains(date)
+    }
+}
+
+/// 某年的某个月，显示为 `2025-03`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearMonth(NaiveDate);
+
+impl YearMonth {
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, 1).map(Self)
+    }
+
+    pub fn year(self) -> i32 {
+        self.0.year()
+    }
+
+    pub fn month(self) -> u32 {
+        self.0.month()
+    }
+}
+
+impl Period for YearMonth {
+    const PAGE_YEARS: i32 = 1;
+    const COLUMNS: usize = 3;
+    const NAME: &'static str = "月份";
+
+    fn containing(date: NaiveDate) -> Self {
+        Self(first_of_month(date))
+    }
+
+    fn first_day(self) -> NaiveDate {
+        self.0
+    }
+
+    fn last_day(self) -> NaiveDate {
+        last_day_of(self.0, 1)
+    }
+
+    fn cell_label(self) -> String {
+        format!("{}月", self.month())
+    }
+
+    fn page(month: NaiveDate) -> Vec<Self> {
+        (1..=12).filter_map(|m| Self::new(month.year(), m)).collect()
+    }
+
+    fn page_title(month: NaiveDate) -> String {
+        format!("{}年", month.year())
+    }
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year(), self.month())
+    }
+}
+
+/// 某年的某个季度，显示为 `2025-Q1`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quarter(NaiveDate);
+
+impl Quarter {
+    /// `quarter` 取 1 到 4
+    pub fn new(year: i32, quarter: u32) -> Option<Self> {
+        if !(1..=4).contains(&quarter) {
+            return None;
+        }
+        NaiveDate::from_ymd_opt(year, quarter * 3 - 2, 1).map(Self)
+    }
+
+    pub fn year(self) -> i32 {
+        self.0.year()
+    }
+
+    pub fn quarter(self) -> u32 {
+        self.0.month0() / 3 + 1
+    }
+}
+
+impl Period for Quarter {
+    const PAGE_YEARS: i32 = 1;
+    const COLUMNS: usize = 4;
+    const NAME: &'static str = "季度";
+
+    fn containing(date: NaiveDate) -> Self {
+        let month = date.month0() / 3 * 3 + 1;
+        Self(first_of_month(date).with_month(month).unwrap_or(date))
+    }
+
+    fn first_day(self) -> NaiveDate {
+        self.0
+    }
+
+    fn last_day(self) -> NaiveDate {
+        last_day_of(self.0, 3)
+    }
+
+    fn cell_label(self) -> String {
+        format!("Q{}", self.quarter())
+    }
+
+    fn page(month: NaiveDate) -> Vec<Self> {
+        (1..=4).filter_map(|q| Self::new(month.year(), q)).collect()
+    }
+
+    fn page_title(month: NaiveDate) -> String {
+        format!("{}年", month.year())
+    }
+}
+
+impl fmt::Display for Quarter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-Q{}", self.year(), self.quarter())
+    }
+}
+
+/// 某一年，显示为 `2025`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(NaiveDate);
+
+impl Year {
+    pub fn new(year: i32) -> Option<Self> {
+        NaiveDate::from_yo_opt(year, 1).map(Self)
+    }
+
+    pub fn year(self) -> i32 {
+        self.0.year()
+    }
+
+    /// 所在十年的第一年，例如 2025 年为 2020
+    fn decade_start(year: i32) -> i32 {
+        year - year.rem_euclid(10)
+    }
+}
+
+impl Period for Year {
+    const PAGE_YEARS: i32 = 10;
+    const COLUMNS: usize = 3;
+    const NAME: &'static str = "年份";
+
+    fn containing(date: NaiveDate) -> Self {
+        Self(date.with_ordinal(1).unwrap_or(date))
+    }
+
+    fn first_day(self) -> NaiveDate {
+        self.0
+    }
+
+    fn last_day(self) -> NaiveDate {
+        last_day_of(self.0, 12)
+    }
+
+    fn cell_label(self) -> String {
+        self.year().to_string()
+    }
+
+    /// 一页显示一个十年，前后各补一年凑成 4×3
+    fn page(month: NaiveDate) -> Vec<Self> {
+        let start = Self::decade_start(month.year());
+        (start - 1..=start + 10).filter_map(Self::new).collect()
+    }
+
+    fn page_title(month: NaiveDate) -> String {
+        let start = Self::decade_start(month.year());
+        format!("{}-{}", start, start + 9)
+    }
+
+    fn is_on_page(self, month: NaiveDate) -> bool {
+        Self::decade_start(self.year()) == Self::decade_start(month.year())
+    }
+}
+
+impl fmt::Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.year())
+    }
+}
+
+/// ISO 8601 周，显示为 `2025-W09`。
+///
+/// 周一到周日为一周，包含当年第一个周四的那一周为第 1 周，
+/// 因此年初或年末的几天可能属于相邻年份的周，例如 2024-12-30 属于 2025-W01。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Week(NaiveDate);
+
+impl Week {
+    /// `week` 超出该年的周数（52 或 53）时返回 `None`
+    pub fn new(year: i32, week: u32) -> Option<Self> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon).map(Self)
+    }
+
+    /// 日期所在的周；周一超出 `NaiveDate` 范围时取该日期本身
+    pub fn containing(date: NaiveDate) -> Self {
+        let offset = date.weekday().num_days_from_monday() as i64;
+        Self(add_days(date, -offset).unwrap_or(date))
+    }
+
+    /// ISO 年，年初年末可能与日历年不同
+    pub fn year(self) -> i32 {
+        self.0.iso_week().year()
+    }
+
+    pub fn week(self) -> u32 {
+        self.0.iso_week().week()
+    }
+
+    /// 周一
+    pub fn first_day(self) -> NaiveDate {
+        self.0
+    }
+
+    /// 周日，超出范围时为 `NaiveDate::MAX`
+    pub fn last_day(self) -> NaiveDate {
+        add_days(self.0, 6).unwrap_or(NaiveDate::MAX)
+    }
+
+    pub fn range(self) -> DateRange {
+        DateRange::new(self.first_day(), self.last_day())
+    }
+}
+
+impl fmt::Display for Week {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-W{:02}", self.year(), self.week())
+    }
+}
+
+/// 从 `first_day` 起 `months` 个月的最后一天，超出范围时为 `NaiveDate::MAX`
+fn last_day_of(first_day: NaiveDate, months: u32) -> NaiveDate {
+    first_day
+        .checked_add_months(Months::new(months))
+        .and_then(|date| date.pred_opt())
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// 月份、季度、年份选择器的核心状态：当前页与选中的周期。
+///
+/// 页的位置、今天与可选规则都放在内部的 [`Calendar`] 中，不依赖 GPUI。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeriodCalendar<P: Period> {
+    pub calendar: Calendar,
+    pub selected: Option<P>,
+}
+
+impl<P: Period> Default for PeriodCalendar<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Period> PeriodCalendar<P> {
+    /// 以今天所在的周期为当前页和选中值
+    pub fn new() -> Self {
+        Self::with_clock(Clock::System)
+    }
+
+    pub fn with_clock(clock: Clock) -> Self {
+        Self {
+            selected: Some(P::containing(clock.today())),
+            calendar: Calendar::with_clock(clock),
+        }
+    }
+
+    pub fn rules(&self) -> &DateRules {
+        &self.calendar.rules
+    }
+
+    pub fn prev_page(&mut self) {
+        self.calendar.shift_years(-P::PAGE_YEARS);
+    }
+
+    pub fn next_page(&mut self) {
+        self.calendar.shift_years(P::PAGE_YEARS);
+    }
+
+    /// 当前页的全部格子
+    pub fn page(&self) -> Vec<P> {
+        P::page(self.calendar.current_month)
+    }
+
+    pub fn page_title(&self) -> String {
+        P::page_title(self.calendar.current_month)
+    }
+
+    /// 周期内至少有一天满足可选规则
+    pub fn is_selectable(&self, period: P) -> bool {
+        self.calendar.rules.allows_any(period.range())
+    }
+
+    pub fn is_selected(&self, period: P) -> bool {
+        self.selected == Some(period)
+    }
+
+    /// 周期包含今天
+    pub fn is_current(&self, period: P) -> bool {
+        period.contains(&self.calendar.today())
+    }
+
+    pub fn is_on_page(&self, period: P) -> bool {
+        period.is_on_page(self.calendar.current_month)
+    }
+
+    /// 选中周期并翻到它所在的页；不可选时保持不变并返回 `false`
+    pub fn select(&mut self, period: P) -> bool {
+        if !self.is_selectable(period) {
+            return false;
+        }
+        self.selected = Some(period);
+        if !self.is_on_page(period) {
+            self.calendar.current_month = period.first_day();
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.selected = None;
+    }
+}
@@ -0,0 +1,291 @@
+use chrono::NaiveDate;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::clock::Clock;
+use crate::period::{Period, PeriodCalendar, Quarter, Year, YearMonth};
+use crate::popup;
+use crate::rollover::watch_clock;
+use crate::rules::DateRules;
+use crate::theme::{ActiveTheme, Theme, watch_appearance};
+
+/// 选择月份，取值显示为 `2025-03`
+pub type MonthPicker = PeriodPicker<YearMonth>;
+/// 选择季度，取值显示为 `2025-Q1`
+pub type QuarterPicker = PeriodPicker<Quarter>;
+/// 选择年份，弹层按十年翻页
+pub type YearPicker = PeriodPicker<Year>;
+
+/// 按月份、季度或年份选择的选择器，与 [`DatePicker`](crate::DatePicker) 共用输入框、弹层与遮罩。
+///
+/// ```ignore
+/// let picker = cx.new(|_| MonthPicker::new().label("统计月份"));
+/// ```
+pub struct PeriodPicker<P: Period> {
+    id: SharedString,
+    state: PeriodCalendar<P>,
+    label: Option<SharedString>,
+    is_open: bool,
+    show_above: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PeriodPickerEvent<P> {
+    Changed(Option<P>),
+    Opened,
+    Closed,
+}
+
+impl<P: Period> EventEmitter<PeriodPickerEvent<P>> for PeriodPicker<P> {}
+
+impl<P: Period> Default for PeriodPicker<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Period> PeriodPicker<P> {
+    pub fn new() -> Self {
+        Self {
+            id: "period-picker".into(),
+            state: PeriodCalendar::new(),
+            label: None,
+            is_open: false,
+            show_above: false,
+        }
+    }
+
+    /// 组件标识，用作各交互元素调试选择器的前缀，例如 `"{id}-trigger"`
+    pub fn id(mut self, id: impl Into<SharedString>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn show_above(mut self, show_above: bool) -> Self {
+        self.show_above = show_above;
+        self
+    }
+
+    /// 最早/最晚日期与禁用日期；周期内还有一天可选时该周期可选
+    pub fn rules(mut self, rules: DateRules) -> Self {
+        self.state.calendar.rules = rules;
+        self
+    }
+
+    /// 「今天」的来源，同时把当前页与默认选中值重置为今天所在的周期
+    pub fn clock(mut self, clock: Clock) -> Self {
+        let rules = std::mem::take(&mut self.state.calendar.rules);
+        self.state = PeriodCalendar::with_clock(clock);
+        self.state.calendar.rules = rules;
+        self
+    }
+
+    pub fn state(&self) -> &PeriodCalendar<P> {
+        &self.state
+    }
+
+    pub fn value(&self) -> Option<P> {
+        self.state.selected
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn is_shown_above(&self) -> bool {
+        self.show_above
+    }
+
+    pub fn open(&mut self, cx: &mut Context<Self>) {
+        if !self.is_open {
+            self.is_open = true;
+            cx.emit(PeriodPickerEvent::Opened);
+            cx.notify();
+        }
+    }
+
+    pub fn close(&mut self, cx: &mut Context<Self>) {
+        if self.is_open {
+            self.is_open = false;
+            cx.emit(PeriodPickerEvent::Closed);
+            cx.notify();
+        }
+    }
+
+    pub fn toggle(&mut self, cx: &mut Context<Self>) {
+        if self.is_open {
+            self.close(cx);
+        } else {
+            self.open(cx);
+        }
+    }
+
+    /// 选中周期并关闭弹层；不可选的周期被忽略
+    pub fn select(&mut self, period: P, cx: &mut Context<Self>) {
+        let previous = self.state.selected;
+        if !self.state.select(period) {
+            return;
+        }
+        if self.state.selected != previous {
+            cx.emit(PeriodPickerEvent::Changed(self.state.selected));
+        }
+        self.close(cx);
+        cx.notify();
+    }
+
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        if self.state.selected.take().is_some() {
+            cx.emit(PeriodPickerEvent::Changed(None));
+        }
+        cx.notify();
+    }
+
+    fn render_trigger(&self, theme: Theme, cx: &mut Context<Self>) -> Stateful<Div> {
+        let value = self.state.selected;
+        let content = div()
+            .flex_1()
+            .text_color(if value.is_some() {
+                theme.text
+            } else {
+                theme.text_placeholder
+            })
+            .child(value.map_or_else(|| format!("选择{}", P::NAME), |period| period.to_string()));
+
+        popup::trigger(&self.id, theme, content)
+            .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                picker.toggle(cx);
+            }))
+    }
+
+    /// `today` 由调用方每帧取一次，而不是每个格子各取一次
+    fn render_cell(&self, period: P, today: NaiveDate, theme: Theme, cx: &mut Context<Self>) -> Stateful<Div> {
+        let is_selected = self.state.is_selected(period);
+        let is_current = period.contains(&today);
+        let is_selectable = self.state.is_selectable(period);
+        let is_muted = !is_selectable || !self.state.is_on_page(period);
+
+        div()
+            .id(SharedString::from(format!("period-{period}")))
+            .debug_selector(|| format!("{}-period-{period}", self.id))
+            .flex_1()
+            .flex()
+            .items_center()
+            .justify_center()
+            .h(px(56.0))
+            .when(is_selectable, |this| {
+                this.cursor_pointer()
+                    .hover(move |style| style.bg(theme.day_hover))
+            })
+            .on_mouse_down(MouseButton::Left, cx.listener(move |picker, _, _, cx| {
+                picker.select(period, cx);
+            }))
+            .child(
+                div()
+                    .px_3()
+                    .py_1()
+                    .rounded(px(4.0))
+                    .bg(if is_selected {
+                        theme.accent
+                    } else if is_current {
+                        theme.today_bg
+                    } else {
+                        theme.surface
+                    })
+                    .text_color(if is_selected {
+                        theme.accent_text
+                    } else if is_muted {
+                        theme.text_disabled
+                    } else if is_current {
+                        theme.today_text
+                    } else {
+                        theme.text
+                    })
+                    .font_weight(if is_current || is_selected {
+                        FontWeight::SEMIBOLD
+                    } else {
+                        FontWeight::NORMAL
+                    })
+                    .child(period.cell_label())
+            )
+    }
+
+    fn render_popup(&self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let page = self.state.page();
+        let today = self.state.calendar.today();
+
+        popup::popup_frame(theme)
+            .flex_col()
+            .w(px(280.0))
+            .shadow_lg()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_4()
+                    .py_2()
+                    .h(px(40.0))
+                    .bg(theme.header_bg)
+                    .border_b(px(1.0))
+                    .border_color(theme.divider)
+                    .child(
+                        popup::nav_button(&self.id, "prev-page", "«", 28.0, theme)
+                            .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                                picker.state.prev_page();
+                                cx.notify();
+                            }))
+                    )
+                    .child(
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text)
+                            .text_lg()
+                            .child(self.state.page_title())
+                    )
+                    .child(
+                        popup::nav_button(&self.id, "next-page", "»", 28.0, theme)
+                            .on_mouse_down(MouseButton::Left, cx.listener(|picker, _, _, cx| {
+                                picker.state.next_page();
+                                cx.notify();
+                            }))
+                    )
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .p_2()
+                    .children(page.chunks(P::COLUMNS).map(|row| {
+                        div()
+                            .flex()
+                            .children(row.iter().map(|period| self.render_cell(*period, today, theme, cx)))
+                    }))
+            )
+    }
+}
+
+impl<P: Period> Render for PeriodPicker<P> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        watch_appearance(window, cx);
+        let theme = *cx.theme();
+        watch_clock(self.state.calendar.clock, cx);
+
+        div()
+            .relative()
+            .w(px(250.0))
+            .when_some(self.label.clone(), |this, label| this.child(popup::label(label, theme)))
+            .child(self.render_trigger(theme, cx))
+            .when(self.is_open, |this| {
+                let popup = self.render_popup(theme, cx);
+                let mask = popup::mask(&self.id, "mask", window, cx.listener(|picker, _, _, cx| {
+                    picker.close(cx);
+                }));
+                this.child(popup::anchor(popup, self.show_above)).child(mask)
+            })
+    }
+}
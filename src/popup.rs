@@ -0,0 +1,190 @@
+use gpui::*;
+
+use crate::theme::{ActiveTheme, Theme};
+
+const TRIGGER_HEIGHT: f32 = 40.0;
+/// 与输入框的垂直间距，适当加大，避免遮挡输入框
+const POPUP_GAP: f32 = 40.0;
+
+/// 输入框上方的说明文字
+pub(crate) fn label(text: SharedString, theme: Theme) -> Div {
+    div()
+        .text_sm()
+        .text_color(theme.text_muted)
+        .mb_1()
+        .child(text)
+}
+
+/// 输入框：边框、圆角、hover 改边框色，右侧日历图标；点击行为由调用方添加
+pub(crate) fn trigger(id: &SharedString, theme: Theme, content: impl IntoElement) -> Stateful<Div> {
+    div()
+        .id("trigger")
+        .debug_selector(|| format!("{id}-trigger"))
+        .flex()
+        .items_center()
+        .px_4()
+        .py_2()
+        .border(px(1.0))
+        .border_color(theme.border)
+        .rounded_md()
+        .bg(theme.surface)
+        .cursor_pointer()
+        .hover(move |style| style.border_color(theme.accent))
+        .child(content)
+        .child(
+            div()
+                .ml_2()
+                .text_color(theme.text_muted)
+                .child("📅")
+        )
+}
+
+/// 弹层外框，阻止点击穿透到下方的遮罩
+pub(crate) fn popup_frame(theme: Theme) -> Div {
+    div()
+        .flex()
+        .border(px(1.0))
+        .border_color(theme.border)
+        .rounded_lg()
+        .overflow_hidden()
+        .bg(theme.surface)
+        .occlude()
+}
+
+/// 年份、月份等翻页按钮；点击行为由调用方添加
+pub(crate) fn nav_button(
+    id: &SharedString,
+    name: &'static str,
+    icon: &'static str,
+    height: f32,
+    theme: Theme,
+) -> Stateful<Div> {
+    div()
+        .id(name)
+        .debug_selector(|| format!("{id}-{name}"))
+        .flex()
+        .items_center()
+        .justify_center()
+        .w(px(32.0))
+        .h(px(height))
+        .rounded(px(4.0))
+        .cursor_pointer()
+        .hover(move |style| style.bg(theme.button_hover))
+        .child(icon)
+}
+
+/// 把弹层放在输入框下方或上方，间距为输入框高度加间距（40+40=80）。
+///
+/// 弹层延迟到最后绘制，优先级高于 [`mask`]，位于遮罩之上。
+pub(crate) fn anchor(popup: Div, show_above: bool) -> Div {
+    let offset = px(TRIGGER_HEIGHT + POPUP_GAP);
+    if show_above {
+        div()
+            .absolute()
+            .bottom(offset)
+            .left(px(0.0))
+            .mb_1()
+            .child(deferred(anchored().anchor(Corner::BottomLeft).child(popup)).with_priority(1))
+    } else {
+        div()
+            .absolute()
+            .top(offset)
+            .left(px(0.0))
+            .mt_1()
+            .child(deferred(anchored().child(popup)).with_priority(1))
+    }
+}
+
+/// 弹层打开时覆盖整个窗口的透明遮罩，点击时调用 `on_close`；调试选择器为 `"{id}-{name}"`
+pub(crate) fn mask(
+    id: &SharedString,
+    name: &'static str,
+    window: &Window,
+    on_close: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+) -> Deferred {
+    let viewport = window.viewport_size();
+    deferred(
+        anchored()
+            .position(point(px(0.0), px(0.0)))
+            .child(
+                div()
+                    .id(name)
+                    .debug_selector(|| format!("{id}-{name}"))
+                    .w(viewport.width)
+                    .h(viewport.height)
+                    .occlude()
+                    .on_mouse_down(MouseButton::Left, on_close)
+            )
+    )
+}
+
+/// 右键菜单：带自己的遮罩，绘制在弹层和弹层的遮罩之上，点击遮罩时调用 `on_close`
+pub(crate) fn context_menu(
+    id: &SharedString,
+    position: Point<Pixels>,
+    items: Vec<Stateful<Div>>,
+    theme: Theme,
+    window: &Window,
+    on_close: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+) -> Div {
+    let menu = div()
+        .debug_selector(|| format!("{id}-menu"))
+        .flex()
+        .flex_col()
+        .min_w(px(160.0))
+        .py_1()
+        .border(px(1.0))
+        .border_color(theme.border)
+        .rounded_md()
+        .bg(theme.surface)
+        .shadow_lg()
+        .occlude()
+        .children(items);
+
+    div()
+        .child(mask(id, "menu-mask", window, on_close).with_priority(2))
+        .child(deferred(anchored().position(position).snap_to_window().child(menu)).with_priority(3))
+}
+
+/// 右键菜单中的一项；点击行为由调用方添加
+pub(crate) fn menu_item(id: &SharedString, name: String, label: SharedString, theme: Theme) -> Stateful<Div> {
+    div()
+        .id(SharedString::from(name.clone()))
+        .debug_selector(|| format!("{id}-menu-{name}"))
+        .px_3()
+        .py_1()
+        .text_sm()
+        .text_color(theme.text)
+        .cursor_pointer()
+        .hover(move |style| style.bg(theme.button_hover))
+        .child(label)
+}
+
+/// 一行文字的悬停提示
+pub(crate) struct TextTooltip {
+    text: SharedString,
+}
+
+impl Render for TextTooltip {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        let theme = *cx.theme();
+        div()
+            .px_2()
+            .py_1()
+            .border(px(1.0))
+            .border_color(theme.border)
+            .rounded(px(4.0))
+            .bg(theme.surface)
+            .shadow_md()
+            .text_xs()
+            .text_color(theme.text)
+            .child(self.text.clone())
+    }
+}
+
+/// 用于 `.tooltip(..)`；文字在悬停时才生成，渲染大量格子时不必逐个格式化
+pub(crate) fn text_tooltip(
+    text: impl Fn() -> String + 'static,
+) -> impl Fn(&mut Window, &mut App) -> AnyView + 'static {
+    move |_, cx| cx.new(|_| TextTooltip { text: text().into() }).into()
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::calendar::{add_days, first_of_month};
+
+/// 包含两端的日期范围，总是满足 `start <= end`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// 两个端点顺序无关
+    pub fn new(a: NaiveDate, b: NaiveDate) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    pub fn single(date: NaiveDate) -> Self {
+        Self::new(date, date)
+    }
+
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        self.start <= *date && *date <= self.end
+    }
+
+    /// 包含两端的天数
+    pub fn num_days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+
+    /// 紧挨在本范围之前、天数相同的范围
+    pub fn previous_period(&self) -> Option<Self> {
+        let end = self.start.pred_opt()?;
+        let start = add_days(end, 1 - self.num_days())?;
+        Some(Self { start, end })
+    }
+
+    /// 去年同期；2 月 29 日对应到去年的 2 月 28 日
+    pub fn same_period_last_year(&self) -> Option<Self> {
+        Some(Self {
+            start: self.start.checked_sub_months(Months::new(12))?,
+            end: self.end.checked_sub_months(Months::new(12))?,
+        })
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ~ {}", self.start, self.end)
+    }
+}
+
+/// 对比模式下对比范围的来源
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CompareMode {
+    /// 主范围之前、天数相同的一段
+    #[default]
+    PreviousPeriod,
+    /// 主范围的去年同期
+    SamePeriodLastYear,
+    /// 在日历上手动选择
+    Custom,
+}
+
+impl CompareMode {
+    pub const ALL: [CompareMode; 3] = [
+        CompareMode::PreviousPeriod,
+        CompareMode::SamePeriodLastYear,
+        CompareMode::Custom,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CompareMode::PreviousPeriod => "上一周期",
+            CompareMode::SamePeriodLastYear => "去年同期",
+            CompareMode::Custom => "自定义",
+        }
+    }
+
+    /// 由主范围推导对比范围，`Custom` 或超出日期范围时为 `None`
+    pub fn derive(self, primary: DateRange) -> Option<DateRange> {
+        match self {
+            CompareMode::PreviousPeriod => primary.previous_period(),
+            CompareMode::SamePeriodLastYear => primary.same_period_last_year(),
+            CompareMode::Custom => None,
+        }
+    }
+}
+
+/// 范围选择器侧边栏的快捷选项，以选择器时钟的「今天」为基准
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RangePreset {
+    Today,
+    Yesterday,
+    /// 含今天在内的最近 7 天
+    Last7Days,
+    /// 含今天在内的最近 30 天
+    Last30Days,
+    /// 本月 1 号至今天
+    ThisMonth,
+    /// 上个月整月
+    LastMonth,
+    /// 本季度第一天至今天
+    ThisQuarter,
+    /// 1 月 1 日至今天
+    YearToDate,
+}
+
+impl RangePreset {
+    pub const ALL: [RangePreset; 8] = [
+        RangePreset::Today,
+        RangePreset::Yesterday,
+        RangePreset::Last7Days,
+        RangePreset::Last30Days,
+        RangePreset::ThisMonth,
+        RangePreset::LastMonth,
+        RangePreset::ThisQuarter,
+        RangePreset::YearToDate,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RangePreset::Today => "今天",
+            RangePreset::Yesterday => "昨天",
+            RangePreset::Last7Days => "最近 7 天",
+            RangePreset::Last30Days => "最近 30 天",
+            RangePreset::ThisMonth => "本月",
+            RangePreset::LastMonth => "上月",
+            RangePreset::ThisQuarter => "本季度",
+            RangePreset::YearToDate => "今年至今",
+        }
+    }
+
+    /// 计算快捷选项对应的范围，靠近 `NaiveDate` 边界时截断到可表示的日期
+    pub fn resolve(self, today: NaiveDate) -> DateRange {
+        let days_ago = |days: i64| add_days(today, -days).unwrap_or(NaiveDate::MIN);
+        match self {
+            RangePreset::Today => DateRange::single(today),
+            RangePreset::Yesterday => DateRange::single(days_ago(1)),
+            RangePreset::Last7Days => DateRange::new(days_ago(6), today),
+            RangePreset::Last30Days => DateRange::new(days_ago(29), today),
+            RangePreset::ThisMonth => DateRange::new(first_of_month(today), today),
+            RangePreset::LastMonth => match first_of_month(today).pred_opt() {
+                Some(end) => DateRange::new(first_of_month(end), end),
+                None => DateRange::new(first_of_month(today), today),
+            },
+            RangePreset::ThisQuarter => {
+                let quarter_start = first_of_month(today)
+                    .with_month0(today.month0() / 3 * 3)
+                    .unwrap_or(today);
+                DateRange::new(quarter_start, today)
+            }
+            RangePreset::YearToDate => {
+                let year_start = first_of_month(today).with_month(1).unwrap_or(today);
+                DateRange::new(year_start, today)
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::calendar::add_days;
+use crate::locale::Locale;
+use crate::period::Week;
+use crate::range::DateRange;
+
+/// 相对今天的描述方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RelativeStyle {
+    /// 「3 天后」「上周二」「2 个月前」
+    #[default]
+    Relative,
+    /// 截止日期：过去的日期显示为「已过期 5 天」，今天为「今天到期」
+    Deadline,
+}
+
+/// 用相对今天的说法描述日期，例如「3 天后」「上周二」、`in 2 weeks`。
+///
+/// 未来的日期按天、周、月、年递进；过去一周之内的日期用星期称呼，更早的按周、月、年递进。
+pub fn describe_relative(date: NaiveDate, today: NaiveDate, locale: Locale, style: RelativeStyle) -> String {
+    let days = (date - today).num_days();
+    if style == RelativeStyle::Deadline && days <= 0 {
+        return match (locale, days) {
+            (Locale::Zh, 0) => "今天到期".to_string(),
+            (Locale::En, 0) => "due today".to_string(),
+            (Locale::Zh, _) => format!("已过期 {} 天", -days),
+            (Locale::En, _) => format!("overdue by {}", plural(-days, "day")),
+        };
+    }
+
+    match days {
+        0 => return phrase(locale, "今天", "today"),
+        1 => return phrase(locale, "明天", "tomorrow"),
+        -1 => return phrase(locale, "昨天", "yesterday"),
+        _ => {}
+    }
+
+    if days < 0 {
+        let weekday = locale.weekday_name(date.weekday());
+        let week = Week::containing(date).first_day();
+        let this_week = Week::containing(today).first_day();
+        if week == this_week {
+            return match locale {
+                Locale::Zh => format!("本{weekday}"),
+                Locale::En => format!("this {weekday}"),
+            };
+        }
+        if Some(week) == add_days(this_week, -7) {
+            return match locale {
+                Locale::Zh => format!("上{weekday}"),
+                Locale::En => format!("last {weekday}"),
+            };
+        }
+    }
+
+    let (count, unit) = magnitude(date.min(today), date.max(today));
+    match (locale, days > 0) {
+        (Locale::Zh, true) => format!("{count} {}后", unit.zh()),
+        (Locale::Zh, false) => format!("{count} {}前", unit.zh()),
+        (Locale::En, true) => format!("in {}", plural(count, unit.en())),
+        (Locale::En, false) => format!("{} ago", plural(count, unit.en())),
+    }
+}
+
+/// 描述一段范围：包含今天时为「进行中」，已结束的按结束日描述，否则按开始日描述
+pub fn describe_relative_range(range: DateRange, today: NaiveDate, locale: Locale, style: RelativeStyle) -> String {
+    if range.start != range.end && range.contains(&today) {
+        return phrase(locale, "进行中", "ongoing");
+    }
+    let date = if range.end < today { range.end } else { range.start };
+    describe_relative(date, today, locale, style)
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Unit {
+    fn zh(self) -> &'static str {
+        match self {
+            Unit::Day => "天",
+            Unit::Week => "周",
+            Unit::Month => "个月",
+            Unit::Year => "年",
+        }
+    }
+
+    fn en(self) -> &'static str {
+        match self {
+            Unit::Day => "day",
+            Unit::Week => "week",
+            Unit::Month => "month",
+            Unit::Year => "year",
+        }
+    }
+}
+
+/// 两个日期之间的距离，取能表示为至少 1 的最大单位：7 天以内按天，30 天以内按周，一年以内按月
+fn magnitude(earlier: NaiveDate, later: NaiveDate) -> (i64, Unit) {
+    let days = (later - earlier).num_days();
+    if days < 7 {
+        return (days, Unit::Day);
+    }
+    if days < 30 {
+        return (days / 7, Unit::Week);
+    }
+    let mut months = (later.year() as i64 * 12 + later.month0() as i64)
+        - (earlier.year() as i64 * 12 + earlier.month0() as i64);
+    if later.day() < earlier.day() {
+        months -= 1;
+    }
+    match months {
+        ..=0 => (days / 7, Unit::Week),
+        1..=11 => (months, Unit::Month),
+        _ => (months / 12, Unit::Year),
+    }
+}
+
+fn phrase(locale: Locale, zh: &str, en: &str) -> String {
+    match locale {
+        Locale::Zh => zh.to_string(),
+        Locale::En => en.to_string(),
+    }
+}
+
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{count} {unit}s")
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use gpui::*;
+
+use crate::clock::Clock;
+
+/// 最长的核对间隔；系统休眠唤醒、修改系统时间或时区后，最迟这么久就能发现日期变化
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 跨天时重绘所有窗口，让「今天」的高亮与相对说明不必等到下一次交互。
+///
+/// 各选择器渲染时登记自己的时钟，释放时注销；定时任务睡到这些时钟中最早的下一个 0 点，
+/// 醒来后发现任何时钟的「今天」变了就重绘所有窗口，然后重新定时。
+#[derive(Default)]
+pub struct DayRollover {
+    /// 每个组件登记的时钟与上次看到的「今天」
+    clocks: HashMap<EntityId, (Clock, NaiveDate)>,
+    task: Option<Task<()>>,
+}
+
+impl Global for DayRollover {}
+
+impl DayRollover {
+    /// 已登记的时钟，多个组件共用的时钟只出现一次
+    pub fn clocks(cx: &App) -> Vec<Clock> {
+        let mut clocks = Vec::new();
+        for (clock, _) in cx.try_global::<Self>().into_iter().flat_map(|rollover| rollover.clocks.values()) {
+            if !clocks.contains(clock) {
+                clocks.push(*clock);
+            }
+        }
+        clocks
+    }
+
+    /// 距离下一次核对的时长：最早的下一个 0 点，最长 [`MAX_CHECK_INTERVAL`]
+    fn next_delay(&self) -> Duration {
+        self.clocks
+            .values()
+            .filter_map(|(clock, _)| clock.until_next_day())
+            .min()
+            .unwrap_or(MAX_CHECK_INTERVAL)
+            .min(MAX_CHECK_INTERVAL)
+    }
+
+    /// 更新各时钟的「今天」，返回是否有时钟跨天
+    fn roll(&mut self) -> bool {
+        let mut changed = false;
+        for (clock, today) in self.clocks.values_mut() {
+            let now = clock.today();
+            if now != *today {
+                *today = now;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// 为渲染中的组件登记时钟，重复登记同一时钟不做任何事；
+/// 组件改用的时钟可能更早跨天，登记后重新定时。组件释放时自动注销
+pub fn watch_clock<T: 'static>(clock: Clock, cx: &mut Context<T>) {
+    let id = cx.entity_id();
+    let rollover = cx.default_global::<DayRollover>();
+    let is_new = match rollover.clocks.get(&id) {
+        Some((watched, _)) if *watched == clock => return,
+        Some(_) => false,
+        None => true,
+    };
+    rollover.clocks.insert(id, (clock, clock.today()));
+    if is_new {
+        cx.on_release(move |_, cx| {
+            if cx.has_global::<DayRollover>() {
+                cx.global_mut::<DayRollover>().clocks.remove(&id);
+            }
+        })
+        .detach();
+    }
+    rearm(cx);
+}
+
+/// 替换定时任务，旧任务随之取消
+fn rearm(cx: &mut App) {
+    let task = cx.spawn(async move |cx| {
+        loop {
+            let Ok(delay) = cx.update(|cx| cx.global::<DayRollover>().next_delay()) else {
+                break;
+            };
+            cx.background_executor().timer(delay).await;
+            // 应用已退出时 update 返回错误
+            let app_gone = cx
+                .update(|cx| {
+                    if cx.global_mut::<DayRollover>().roll() {
+                        cx.refresh_windows();
+                    }
+                })
+                .is_err();
+            if app_gone {
+                break;
+            }
+        }
+    });
+    cx.global_mut::<DayRollover>().task = Some(task);
+}
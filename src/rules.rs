@@ -0,0 +1,72 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::range::DateRange;
+use crate::selection::DateValue;
+
+/// 可选日期的限制：最早/最晚日期，以及禁用的星期与具体日期。
+///
+/// 日期选择器按天判断；月份、季度、年份选择器只要周期内还有一天可选，该周期就可选。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DateRules {
+    pub min_date: Option<NaiveDate>,
+    pub max_date: Option<NaiveDate>,
+    pub disabled_weekdays: Vec<Weekday>,
+    pub disabled_dates: Vec<NaiveDate>,
+}
+
+impl DateRules {
+    pub fn min_date(mut self, date: NaiveDate) -> Self {
+        self.min_date = Some(date);
+        self
+    }
+
+    pub fn max_date(mut self, date: NaiveDate) -> Self {
+        self.max_date = Some(date);
+        self
+    }
+
+    pub fn disable_weekdays(mut self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        self.disabled_weekdays.extend(weekdays);
+        self
+    }
+
+    pub fn disable_dates(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.disabled_dates.extend(dates);
+        self
+    }
+
+    /// 日期在最早/最晚日期之间，且没有被禁用
+    pub fn is_allowed(&self, date: &NaiveDate) -> bool {
+        self.min_date.is_none_or(|min| min <= *date)
+            && self.max_date.is_none_or(|max| *date <= max)
+            && !self.disabled_weekdays.contains(&date.weekday())
+            && !self.disabled_dates.contains(date)
+    }
+
+    /// 整个取值能否选中：日期与范围的端点都要可选，周内至少有一天可选
+    pub fn allows_value(&self, value: &DateValue) -> bool {
+        let allows_range = |range: &DateRange| self.is_allowed(&range.start) && self.is_allowed(&range.end);
+        match value {
+            DateValue::Date(date) => self.is_allowed(date),
+            DateValue::Range(range) => allows_range(range),
+            DateValue::Comparison { primary, compare } => allows_range(primary) && allows_range(compare),
+            DateValue::Week(week) => self.allows_any(week.range()),
+        }
+    }
+
+    /// 范围内至少有一天可选
+    pub fn allows_any(&self, range: DateRange) -> bool {
+        let start = self.min_date.map_or(range.start, |min| min.max(range.start));
+        let end = self.max_date.map_or(range.end, |max| max.min(range.end));
+        if start > end {
+            return false;
+        }
+        if self.disabled_weekdays.is_empty() && self.disabled_dates.is_empty() {
+            return true;
+        }
+        start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .any(|date| self.is_allowed(&date))
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::period::Week;
+use crate::range::DateRange;
+
+/// 选择器的选择模式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// 单个日期
+    #[default]
+    Single,
+    /// 起止日期范围，两次点击分别确定两个端点
+    Range,
+    /// 主范围加一段对比范围，对比范围可手动选择或自动推导
+    Comparison,
+    /// 整个 ISO 周，网格每行从周一开始，点击任意一天选中所在的一行
+    Week,
+}
+
+/// 对比模式下日历点击作用于哪一段范围
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompareTarget {
+    #[default]
+    Primary,
+    Compare,
+}
+
+/// 选择器的取值，与 [`SelectionMode`] 一一对应
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateValue {
+    Date(NaiveDate),
+    Range(DateRange),
+    Comparison {
+        primary: DateRange,
+        compare: DateRange,
+    },
+    Week(Week),
+}
+
+impl DateValue {
+    /// 取值覆盖的日期：单个日期为当天，周为周一到周日，对比取主范围
+    pub fn span(&self) -> DateRange {
+        match self {
+            DateValue::Date(date) => DateRange::single(*date),
+            DateValue::Range(range) => *range,
+            DateValue::Comparison { primary, .. } => *primary,
+            DateValue::Week(week) => week.range(),
+        }
+    }
+
+    /// ISO 8601 表示：日期 `2025-03-14`，范围为区间 `2025-03-01/2025-03-07`，周 `2025-W09`，
+    /// 对比为以逗号分隔的主范围与对比范围 `2025-03-01/2025-03-07,2025-02-22/2025-02-28`
+    pub fn to_iso_string(&self) -> String {
+        let interval = |range: &DateRange| format!("{}/{}", range.start, range.end);
+        match self {
+            DateValue::Date(date) => date.to_string(),
+            DateValue::Range(range) => interval(range),
+            DateValue::Comparison { primary, compare } => format!("{},{}", interval(primary), interval(compare)),
+            DateValue::Week(week) => week.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateValue::Date(date) => write!(f, "{date}"),
+            DateValue::Range(range) => write!(f, "{range}"),
+            DateValue::Comparison { primary, compare } => write!(f, "{primary} 对比 {compare}"),
+            DateValue::Week(week) => write!(f, "{week}"),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use gpui::*;
+
+/// 主题模式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    HighContrast,
+    /// 跟随窗口（系统）外观，在浅色与深色之间切换
+    System,
+}
+
+/// 日历组件用到的全部颜色，按用途命名
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    pub background: Hsla,
+    pub surface: Hsla,
+    pub header_bg: Hsla,
+    pub subheader_bg: Hsla,
+    pub border: Hsla,
+    pub divider: Hsla,
+    pub button_hover: Hsla,
+    pub day_hover: Hsla,
+    pub accent: Hsla,
+    pub accent_text: Hsla,
+    pub today_bg: Hsla,
+    pub today_text: Hsla,
+    /// 范围模式中两端之间日期的底色与文字
+    pub range_bg: Hsla,
+    pub range_text: Hsla,
+    /// 对比范围的端点、范围内底色与文字
+    pub compare_accent: Hsla,
+    pub compare_accent_text: Hsla,
+    pub compare_bg: Hsla,
+    pub compare_text: Hsla,
+    /// 键盘光标所在日期的描边
+    pub focus_ring: Hsla,
+    /// 年视图中有事件、节假日的日期的底色与文字
+    pub event_bg: Hsla,
+    pub event_text: Hsla,
+    pub holiday_bg: Hsla,
+    pub holiday_text: Hsla,
+    /// 热力图没有数据的格子，以及最高一级的颜色；中间各级按透明度插值
+    pub heat_empty: Hsla,
+    pub heat_full: Hsla,
+    pub text: Hsla,
+    pub text_muted: Hsla,
+    pub text_placeholder: Hsla,
+    pub text_disabled: Hsla,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Self {
+            background: rgb(0xf8fafc).into(),
+            surface: rgb(0xffffff).into(),
+            header_bg: rgb(0xf3f4f6).into(),
+            subheader_bg: rgb(0xf9fafb).into(),
+            border: rgb(0xd1d5db).into(),
+            divider: rgb(0xe5e7eb).into(),
+            button_hover: rgb(0xe5e7eb).into(),
+            day_hover: rgb(0xf3f4f6).into(),
+            accent: rgb(0x3b82f6).into(),
+            accent_text: rgb(0xffffff).into(),
+            today_bg: rgb(0xeff6ff).into(),
+            today_text: rgb(0x3b82f6).into(),
+            range_bg: rgb(0xdbeafe).into(),
+            range_text: rgb(0x1e40af).into(),
+            compare_accent: rgb(0xf97316).into(),
+            compare_accent_text: rgb(0xffffff).into(),
+            compare_bg: rgb(0xffedd5).into(),
+            compare_text: rgb(0xc2410c).into(),
+            focus_ring: rgb(0x1d4ed8).into(),
+            event_bg: rgb(0xdcfce7).into(),
+            event_text: rgb(0x15803d).into(),
+            holiday_bg: rgb(0xfee2e2).into(),
+            holiday_text: rgb(0xb91c1c).into(),
+            heat_empty: rgb(0xebedf0).into(),
+            heat_full: rgb(0x216e39).into(),
+            text: rgb(0x111827).into(),
+            text_muted: rgb(0x6b7280).into(),
+            text_placeholder: rgb(0x9ca3af).into(),
+            text_disabled: rgb(0xd1d5db).into(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            background: rgb(0x0f172a).into(),
+            surface: rgb(0x1f2937).into(),
+            header_bg: rgb(0x111827).into(),
+            subheader_bg: rgb(0x182130).into(),
+            border: rgb(0x374151).into(),
+            divider: rgb(0x374151).into(),
+            button_hover: rgb(0x374151).into(),
+            day_hover: rgb(0x2b3544).into(),
+            accent: rgb(0x3b82f6).into(),
+            accent_text: rgb(0xffffff).into(),
+            today_bg: rgb(0x1e3a8a).into(),
+            today_text: rgb(0x93c5fd).into(),
+            range_bg: rgb(0x1e3a5f).into(),
+            range_text: rgb(0xbfdbfe).into(),
+            compare_accent: rgb(0xf97316).into(),
+            compare_accent_text: rgb(0xffffff).into(),
+            compare_bg: rgb(0x4a2511).into(),
+            compare_text: rgb(0xfdba74).into(),
+            focus_ring: rgb(0x93c5fd).into(),
+            event_bg: rgb(0x14532d).into(),
+            event_text: rgb(0x86efac).into(),
+            holiday_bg: rgb(0x7f1d1d).into(),
+            holiday_text: rgb(0xfca5a5).into(),
+            heat_empty: rgb(0x2d333b).into(),
+            heat_full: rgb(0x39d353).into(),
+            text: rgb(0xf9fafb).into(),
+            text_muted: rgb(0x9ca3af).into(),
+            text_placeholder: rgb(0x6b7280).into(),
+            text_disabled: rgb(0x4b5563).into(),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            background: rgb(0x000000).into(),
+            surface: rgb(0x000000).into(),
+            header_bg: rgb(0x000000).into(),
+            subheader_bg: rgb(0x000000).into(),
+            border: rgb(0xffffff).into(),
+            divider: rgb(0xffffff).into(),
+            button_hover: rgb(0x333333).into(),
+            day_hover: rgb(0x333333).into(),
+            accent: rgb(0xffff00).into(),
+            accent_text: rgb(0x000000).into(),
+            today_bg: rgb(0x000000).into(),
+            today_text: rgb(0x00ffff).into(),
+            range_bg: rgb(0x333300).into(),
+            range_text: rgb(0xffff00).into(),
+            compare_accent: rgb(0xff00ff).into(),
+            compare_accent_text: rgb(0x000000).into(),
+            compare_bg: rgb(0x330033).into(),
+            compare_text: rgb(0xff80ff).into(),
+            focus_ring: rgb(0x00ff00).into(),
+            event_bg: rgb(0x003300).into(),
+            event_text: rgb(0x00ff00).into(),
+            holiday_bg: rgb(0x330000).into(),
+            holiday_text: rgb(0xff8080).into(),
+            heat_empty: rgb(0x333333).into(),
+            heat_full: rgb(0x00ff00).into(),
+            text: rgb(0xffffff).into(),
+            text_muted: rgb(0xe0e0e0).into(),
+            text_placeholder: rgb(0xc0c0c0).into(),
+            text_disabled: rgb(0x9e9e9e).into(),
+        }
+    }
+
+    /// 把主题模式解析为具体配色，`System` 依据窗口外观决定
+    pub fn resolve(mode: ThemeMode, appearance: WindowAppearance) -> Self {
+        match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+            ThemeMode::HighContrast => Self::high_contrast(),
+            ThemeMode::System => match appearance {
+                WindowAppearance::Dark | WindowAppearance::VibrantDark => Self::dark(),
+                WindowAppearance::Light | WindowAppearance::VibrantLight => Self::light(),
+            },
+        }
+    }
+}
+
+/// 当前生效的主题，作为全局状态供所有视图读取
+pub struct ThemeState {
+    pub mode: ThemeMode,
+    pub theme: Theme,
+    /// 已监听外观变化的窗口
+    windows: HashSet<WindowId>,
+}
+
+impl Global for ThemeState {}
+
+impl ThemeState {
+    /// 已监听外观变化的窗口，窗口关闭后移除
+    pub fn watched_windows(cx: &App) -> Vec<WindowId> {
+        cx.try_global::<Self>()
+            .map_or_else(Vec::new, |state| state.windows.iter().copied().collect())
+    }
+}
+
+/// 尚未安装 [`ThemeState`] 时使用的配色
+static FALLBACK_THEME: LazyLock<Theme> = LazyLock::new(Theme::light);
+
+/// 安装默认的主题状态：跟随系统外观；已有主题时不做处理
+pub(crate) fn init(cx: &mut App) {
+    if !cx.has_global::<ThemeState>() {
+        let mode = ThemeMode::System;
+        cx.set_global(ThemeState {
+            mode,
+            theme: Theme::resolve(mode, cx.window_appearance()),
+            windows: HashSet::new(),
+        });
+        // 窗口关闭后不再记着它，只保留仍然打开的窗口
+        cx.on_window_closed(|cx| {
+            let open: HashSet<WindowId> = cx.windows().iter().map(|window| window.window_id()).collect();
+            cx.global_mut::<ThemeState>().windows.retain(|id| open.contains(id));
+        })
+        .detach();
+    }
+}
+
+/// 跟随系统时，窗口外观变化后重新解析主题。组件渲染时调用，每个窗口只监听一次
+pub fn watch_appearance(window: &mut Window, cx: &mut App) {
+    init(cx);
+    let id = window.window_handle().window_id();
+    if !cx.global_mut::<ThemeState>().windows.insert(id) {
+        return;
+    }
+    window
+        .observe_window_appearance(|window, cx| {
+            if cx.global::<ThemeState>().mode == ThemeMode::System {
+                set_theme_mode(ThemeMode::System, window, cx);
+            }
+        })
+        .detach();
+}
+
+/// 从 `App` 读取当前主题：`cx.theme()`
+pub trait ActiveTheme {
+    fn theme(&self) -> &Theme;
+}
+
+impl ActiveTheme for App {
+    fn theme(&self) -> &Theme {
+        self.try_global::<ThemeState>()
+            .map_or(&FALLBACK_THEME, |state| &state.theme)
+    }
+}
+
+/// 切换主题并立即重绘所有窗口
+pub fn set_theme_mode(mode: ThemeMode, window: &Window, cx: &mut App) {
+    init(cx);
+    let state = cx.global_mut::<ThemeState>();
+    state.mode = mode;
+    state.theme = Theme::resolve(mode, window.appearance());
+    cx.refresh_windows();
+}
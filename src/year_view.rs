@@ -0,0 +1,305 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::calendar::{Calendar, GridCache, first_of_month};
+use crate::date_picker::{DatePicker, DatePickerEvent};
+use crate::locale::Locale;
+use crate::popup;
+use crate::rollover::watch_clock;
+use crate::selection::DateValue;
+use crate::theme::{ActiveTheme, Theme, watch_appearance};
+
+const MINI_CELL_SIZE: f32 = 24.0;
+
+/// 年视图每行排几个月：3×4、4×3 或 6×2
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum YearColumns {
+    Three,
+    #[default]
+    Four,
+    Six,
+}
+
+impl YearColumns {
+    pub fn count(self) -> usize {
+        match self {
+            YearColumns::Three => 3,
+            YearColumns::Four => 4,
+            YearColumns::Six => 6,
+        }
+    }
+}
+
+/// 一年 12 个月的小月历，标出事件与节假日，适合排休假等按年规划的场景。
+///
+/// 点击某天会在传入的 [`DatePicker`] 弹层中打开该月，由用户在弹层中完成选择；
+/// 时钟、规则、节假日与语言都取自这个选择器并随之更新，年视图标出选择器已提交的日期：
+///
+/// ```ignore
+/// let picker = cx.new(|cx| DatePicker::new(cx).working_days(holidays));
+/// let year = cx.new(|cx| YearView::new(picker, cx).events(leave_days));
+/// ```
+pub struct YearView {
+    id: SharedString,
+    calendar: Calendar,
+    /// 星期与月份标题的语言，取自选择器
+    locale: Locale,
+    columns: YearColumns,
+    events: BTreeSet<NaiveDate>,
+    picker: Entity<DatePicker>,
+    grid_cache: RefCell<GridCache>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl YearView {
+    pub fn new(picker: Entity<DatePicker>, cx: &mut Context<Self>) -> Self {
+        let mut calendar = picker.read(cx).calendar().clone();
+        calendar.selected_date = committed_date(picker.read(cx));
+        let locale = picker.read(cx).current_locale();
+        let observation = cx.observe(&picker, |view, picker, cx| {
+            view.sync_settings(picker.read(cx));
+            cx.notify();
+        });
+        let subscription = cx.subscribe(&picker, |view, picker, event: &DatePickerEvent, cx| {
+            if let DatePickerEvent::Changed(_) = event {
+                view.calendar.selected_date = committed_date(picker.read(cx));
+                // 在弹层里换到别的年份时，年视图跟着翻过去
+                if let Some(date) = view.calendar.selected_date
+                    && date.year() != view.calendar.current_month.year()
+                {
+                    view.calendar.current_month = first_of_month(date);
+                }
+                cx.notify();
+            }
+        });
+
+        Self {
+            id: "year-view".into(),
+            calendar,
+            locale,
+            columns: YearColumns::default(),
+            events: BTreeSet::new(),
+            picker,
+            grid_cache: RefCell::default(),
+            _subscriptions: vec![observation, subscription],
+        }
+    }
+
+    /// 从选择器取时钟、规则、节假日、网格选项、语言与已提交的日期；年视图自己显示的年份不变
+    fn sync_settings(&mut self, picker: &DatePicker) {
+        self.calendar = Calendar {
+            current_month: self.calendar.current_month,
+            selected_date: committed_date(picker),
+            ..picker.calendar().clone()
+        };
+        self.locale = picker.current_locale();
+    }
+
+    /// 组件标识，用作各交互元素调试选择器的前缀，例如 `"{id}-day-2025-03-14"`
+    pub fn id(mut self, id: impl Into<SharedString>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn columns(mut self, columns: YearColumns) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// 有事件的日期，以事件底色标出
+    pub fn events(mut self, events: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.events.extend(events);
+        self
+    }
+
+    pub fn set_columns(&mut self, columns: YearColumns, cx: &mut Context<Self>) {
+        self.columns = columns;
+        cx.notify();
+    }
+
+    pub fn set_events(&mut self, events: impl IntoIterator<Item = NaiveDate>, cx: &mut Context<Self>) {
+        self.events = events.into_iter().collect();
+        cx.notify();
+    }
+
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    pub fn selected_date(&self) -> Option<NaiveDate> {
+        self.calendar.selected_date
+    }
+
+    pub fn picker(&self) -> &Entity<DatePicker> {
+        &self.picker
+    }
+
+    /// 在选择器弹层中打开 `date` 所在的月份，由用户在弹层中完成选择；不满足规则的日期被忽略
+    pub fn select(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        if !self.calendar.rules.is_allowed(&date) {
+            return;
+        }
+        self.picker.update(cx, |picker, cx| {
+            picker.show_month(date, cx);
+            picker.open(cx);
+        });
+    }
+
+    fn render_header(&self, theme: Theme, cx: &mut Context<Self>) -> Div {
+        let nav = |name: &'static str, icon: &'static str, on_click: fn(&mut Calendar)| {
+            popup::nav_button(&self.id, name, icon, 28.0, theme)
+                .on_mouse_down(MouseButton::Left, cx.listener(move |view, _, _, cx| {
+                    on_click(&mut view.calendar);
+                    cx.notify();
+                }))
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(nav("prev-year", "«", Calendar::prev_year))
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.text)
+                    .text_lg()
+                    .child(format!("{}年", self.calendar.current_month.year()))
+            )
+            .child(nav("next-year", "»", Calendar::next_year))
+            .child(div().flex_1())
+            .child(self.picker.clone())
+    }
+
+    fn render_mini_day(
+        &self,
+        date: NaiveDate,
+        today: NaiveDate,
+        theme: Theme,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        let is_selected = self.calendar.selected_date == Some(date);
+        let is_today = date == today;
+        let is_holiday = self.calendar.working_days.holidays.contains(&date);
+        let is_event = self.events.contains(&date);
+        let is_allowed = self.calendar.rules.is_allowed(&date);
+
+        let (bg, text) = if is_selected {
+            (theme.accent, theme.accent_text)
+        } else if !is_allowed {
+            (theme.surface, theme.text_disabled)
+        } else if is_holiday {
+            (theme.holiday_bg, theme.holiday_text)
+        } else if is_event {
+            (theme.event_bg, theme.event_text)
+        } else if is_today {
+            (theme.today_bg, theme.today_text)
+        } else {
+            (theme.surface, theme.text)
+        };
+
+        div()
+            .id(SharedString::from(format!("day-{date}")))
+            .debug_selector(|| format!("{}-day-{date}", self.id))
+            .flex()
+            .items_center()
+            .justify_center()
+            .size(px(MINI_CELL_SIZE))
+            .rounded(px(4.0))
+            .text_xs()
+            .bg(bg)
+            .text_color(text)
+            .when(is_today, |this| this.border_1().border_color(theme.today_text))
+            .when(is_allowed, |this| {
+                this.cursor_pointer()
+                    .hover(move |style| style.bg(theme.day_hover))
+            })
+            .on_mouse_down(MouseButton::Left, cx.listener(move |view, _, _, cx| {
+                view.select(date, cx);
+            }))
+            .child(date.day().to_string())
+    }
+
+    /// 一个月的小月历：月份标题、星期标题和固定 6 行日期，隐藏相邻月份
+    fn render_mini_month(
+        &self,
+        month: NaiveDate,
+        today: NaiveDate,
+        theme: Theme,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let mut view = self.calendar.month_view(month);
+        view.grid_options.trim_trailing_rows = false;
+        let days = self.grid_cache.borrow_mut().get(&view);
+        let week_start = view.week_start();
+        let cell = || div().flex().items_center().justify_center().size(px(MINI_CELL_SIZE));
+
+        div()
+            .flex()
+            .flex_col()
+            .p_2()
+            .border(px(1.0))
+            .border_color(theme.border)
+            .rounded_md()
+            .bg(theme.surface)
+            .child(
+                div()
+                    .mb_1()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_sm()
+                    .text_color(theme.text)
+                    .child(self.locale.month_short_name(month.month()))
+            )
+            .child(
+                div()
+                    .flex()
+                    .text_xs()
+                    .text_color(theme.text_muted)
+                    .children(
+                        std::iter::successors(Some(week_start), |day| Some(day.succ()))
+                            .take(7)
+                            .map(|weekday| cell().child(self.locale.weekday_short_name(weekday)))
+                    )
+            )
+            .children(days.chunks(7).map(|week| {
+                div().flex().children(week.iter().map(|date_opt| match *date_opt {
+                    Some(date) => self.render_mini_day(date, today, theme, cx).into_any_element(),
+                    None => cell().into_any_element(),
+                }))
+            }))
+    }
+}
+
+impl Render for YearView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
+        watch_appearance(window, cx);
+        let theme = *cx.theme();
+        watch_clock(self.calendar.clock, cx);
+        let today = self.calendar.today();
+        let months = self.calendar.year_months();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(self.render_header(theme, cx))
+            .children(months.chunks(self.columns.count()).map(|row| {
+                div()
+                    .flex()
+                    .gap_3()
+                    .children(row.iter().map(|month| self.render_mini_month(*month, today, theme, cx)))
+            }))
+    }
+}
+
+/// 选择器已提交的单个日期；范围等其他取值在年视图中不标出
+fn committed_date(picker: &DatePicker) -> Option<NaiveDate> {
+    match picker.committed_value() {
+        Some(DateValue::Date(date)) => Some(date),
+        _ => None,
+    }
+}
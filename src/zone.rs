@@ -0,0 +1,42 @@
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeDelta, TimeZone};
+use chrono_tz::{OffsetName, Tz};
+
+use crate::range::DateRange;
+
+/// 日期当天最早的时刻，通常是 0 点；夏令时从 0 点开始跳过时取跳过之后的第一个时刻
+pub fn start_of_day<Z: TimeZone>(date: NaiveDate, zone: &Z) -> DateTime<Z> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    // 时区跳变最长不超过一天，逐小时向后找到第一个存在的本地时间
+    (0..=24)
+        .find_map(|hours| {
+            let local = midnight.checked_add_signed(TimeDelta::hours(hours))?;
+            zone.from_local_datetime(&local).earliest()
+        })
+        .unwrap_or_else(|| zone.from_utc_datetime(&midnight))
+}
+
+/// 范围覆盖的时间段 `[start, end)`，`end` 为最后一天的次日 0 点；
+/// 最后一天已是 `NaiveDate::MAX` 时 `end` 取当天 0 点
+pub fn range_instants<Z: TimeZone>(range: DateRange, zone: &Z) -> (DateTime<Z>, DateTime<Z>) {
+    let next = range.end.checked_add_days(Days::new(1)).unwrap_or(range.end);
+    (start_of_day(range.start, zone), start_of_day(next, zone))
+}
+
+/// 同一时刻在另一个时区的本地日期，例如上海的周一早上在纽约仍是周日
+pub fn date_in_zone<Z: TimeZone>(instant: &DateTime<Z>, zone: Tz) -> NaiveDate {
+    instant.with_timezone(&zone).date_naive()
+}
+
+/// 把 `from` 时区某天的 0 点换算到 `to` 时区，得到对方看到的同一时刻
+pub fn convert_start_of_day(date: NaiveDate, from: Tz, to: Tz) -> DateTime<Tz> {
+    start_of_day(date, &from).with_timezone(&to)
+}
+
+/// 时区在该日期 0 点使用的缩写，例如 `CST`、`EDT`；没有缩写时返回 UTC 偏移，例如 `+04`
+pub fn zone_abbreviation(zone: Tz, date: NaiveDate) -> String {
+    let instant = start_of_day(date, &zone);
+    match instant.offset().abbreviation() {
+        Some(abbreviation) => abbreviation.to_string(),
+        None => instant.format("%:z").to_string(),
+    }
+}
@@ -0,0 +1,737 @@
+#[path = "../examples/demo/app_view.rs"]
+mod app_view;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use app_view::AppView;
+use chrono::{Datelike, Days, Months, NaiveDate, Utc, Weekday};
+use gpui::{
+    Bounds, ClipboardItem, Entity, Modifiers, MouseButton, Pixels, TestAppContext, VisualTestContext, point, px,
+};
+use gpui_demo::{
+    Clock, DatePicker, DatePickerEvent, DateRange, DateRules, DateValue, DayRollover, DisplayMode, RangePreset,
+    SelectionMode, ThemeState, Tz, Week, WorkingDays, YearMonth, first_of_month,
+};
+
+/// 示例的「今天」，纽约时区的示例除外；固定下来，测试结果不随运行日期变化
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+}
+
+fn open_app(cx: &mut TestAppContext) -> (Entity<AppView>, &mut VisualTestContext) {
+    cx.update(gpui_demo::init);
+    let (view, cx) = cx.add_window_view(|window, cx| AppView::with_clock(Clock::Fixed(today()), window, cx));
+    cx.run_until_parked();
+    (view, cx)
+}
+
+/// 按调试选择器查找元素；`debug_bounds` 只接受 `&'static str`，所有查找都经过这里，动态拼出的选择器只在此转换
+fn try_find(cx: &mut VisualTestContext, selector: impl Into<String>) -> Option<Bounds<Pixels>> {
+    let selector: &'static str = selector.into().leak();
+    cx.debug_bounds(selector)
+}
+
+fn find(cx: &mut VisualTestContext, selector: impl Into<String>) -> Bounds<Pixels> {
+    let selector = selector.into();
+    try_find(cx, selector.clone()).unwrap_or_else(|| panic!("找不到元素 {selector}"))
+}
+
+fn click(cx: &mut VisualTestContext, selector: impl Into<String>) {
+    let bounds = find(cx, selector);
+    cx.simulate_click(bounds.center(), Modifiers::none());
+    cx.run_until_parked();
+}
+
+/// 点击遮罩的右下角，避开弹层本身
+fn click_mask(cx: &mut VisualTestContext, picker_id: impl Into<String>) {
+    let mask = find(cx, format!("{}-mask", picker_id.into()));
+    cx.simulate_click(mask.bottom_right() - point(px(2.0), px(2.0)), Modifiers::none());
+    cx.run_until_parked();
+}
+
+fn read_picker<R>(
+    view: &Entity<AppView>,
+    idx: usize,
+    cx: &mut VisualTestContext,
+    f: impl FnOnce(&DatePicker) -> R,
+) -> R {
+    view.read_with(cx, |app, cx| f(app.pickers[idx].read(cx)))
+}
+
+fn current_month(view: &Entity<AppView>, idx: usize, cx: &mut VisualTestContext) -> NaiveDate {
+    read_picker(view, idx, cx, |picker| picker.calendar().current_month)
+}
+
+#[gpui::test]
+fn trigger_opens_popup_below(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_open));
+
+    click(cx, "picker-0-trigger");
+    assert!(read_picker(&view, 0, cx, DatePicker::is_open));
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_shown_above));
+    assert!(!read_picker(&view, 1, cx, DatePicker::is_open));
+}
+
+#[gpui::test]
+fn lower_pickers_open_above(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+
+    click(cx, "picker-2-trigger");
+    assert!(read_picker(&view, 2, cx, DatePicker::is_open));
+    assert!(read_picker(&view, 2, cx, DatePicker::is_shown_above));
+}
+
+#[gpui::test]
+fn mask_click_closes_popup(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+
+    click(cx, "picker-0-trigger");
+    click_mask(cx, "picker-0");
+
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_open));
+    assert!(try_find(cx, "picker-0-mask").is_none());
+}
+
+#[gpui::test]
+fn year_and_month_arrows_navigate(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let start = first_of_month(today());
+
+    click(cx, "picker-0-trigger");
+    click(cx, "picker-0-prev-year");
+    assert_eq!(current_month(&view, 0, cx), start - Months::new(12));
+
+    click(cx, "picker-0-next-year");
+    click(cx, "picker-0-next-year");
+    assert_eq!(current_month(&view, 0, cx), start + Months::new(12));
+
+    click(cx, "picker-0-prev-month");
+    assert_eq!(current_month(&view, 0, cx), start + Months::new(11));
+
+    click(cx, "picker-0-next-month");
+    click(cx, "picker-0-next-month");
+    assert_eq!(current_month(&view, 0, cx), start + Months::new(13));
+
+    // 导航不关闭弹层，也不改变选中日期
+    assert!(read_picker(&view, 0, cx, DatePicker::is_open));
+    assert_eq!(
+        read_picker(&view, 0, cx, DatePicker::selected_date),
+        Some(today())
+    );
+}
+
+#[gpui::test]
+fn day_click_selects_and_closes(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let target = first_of_month(today()).with_day(15).unwrap();
+
+    click(cx, "picker-0-trigger");
+    click(cx, format!("picker-0-day-{target}"));
+
+    assert_eq!(read_picker(&view, 0, cx, DatePicker::selected_date), Some(target));
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_open));
+}
+
+#[gpui::test]
+fn adjacent_day_click_respects_grid_options(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let start = first_of_month(today());
+    let next_month_day = start + Months::new(1);
+    let last_cell = read_picker(&view, 0, cx, |picker| {
+        picker.calendar().get_month_days().last().copied().flatten()
+    })
+    .unwrap();
+    assert!(last_cell >= next_month_day);
+
+    // 示例1 不允许选择相邻月份
+    click(cx, "picker-0-trigger");
+    click(cx, format!("picker-0-day-{last_cell}"));
+    assert!(read_picker(&view, 0, cx, DatePicker::is_open));
+    assert_eq!(
+        read_picker(&view, 0, cx, DatePicker::selected_date),
+        Some(today())
+    );
+    assert_eq!(current_month(&view, 0, cx), start);
+    click_mask(cx, "picker-0");
+
+    // 示例2 允许，选中后切换到该日期所在月份
+    click(cx, "picker-1-trigger");
+    click(cx, format!("picker-1-day-{last_cell}"));
+    assert_eq!(read_picker(&view, 1, cx, DatePicker::selected_date), Some(last_cell));
+    assert_eq!(current_month(&view, 1, cx), first_of_month(last_cell));
+    assert_eq!(last_cell.month(), next_month_day.month());
+}
+
+#[gpui::test]
+fn range_picker_presets_and_clicks(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let range_picker = 4;
+    let today = today();
+
+    click(cx, "picker-range-trigger");
+    click(cx, "picker-range-preset-Last7Days");
+    assert!(!read_picker(&view, range_picker, cx, DatePicker::is_open));
+    assert_eq!(
+        read_picker(&view, range_picker, cx, DatePicker::value),
+        Some(DateValue::Range(RangePreset::Last7Days.resolve(today)))
+    );
+    assert_eq!(
+        read_picker(&view, range_picker, cx, |picker| picker.calendar().active_preset()),
+        Some(RangePreset::Last7Days)
+    );
+
+    // 两次点击确定范围，第一次点击后弹层保持打开
+    let first = first_of_month(today);
+    let second = first.with_day(3).unwrap();
+    click(cx, "picker-range-trigger");
+    click(cx, format!("picker-range-day-{second}"));
+    assert!(read_picker(&view, range_picker, cx, DatePicker::is_open));
+    click(cx, format!("picker-range-day-{first}"));
+    assert!(!read_picker(&view, range_picker, cx, DatePicker::is_open));
+    assert_eq!(
+        read_picker(&view, range_picker, cx, DatePicker::value),
+        Some(DateValue::Range(DateRange::new(first, second)))
+    );
+    assert_eq!(
+        read_picker(&view, range_picker, cx, DatePicker::working_day_count),
+        Some(WorkingDays::default().count(DateRange::new(first, second)))
+    );
+}
+
+#[gpui::test]
+fn range_picker_drag_selects_and_auto_advances(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let range_picker = 4;
+    let today = today();
+    let first = first_of_month(today);
+    let center = |cx: &mut VisualTestContext, selector: String| find(cx, selector).center();
+
+    // 从 1 号拖到 5 号，拖动途中显示预览，松开即完成选择
+    click(cx, "picker-range-trigger");
+    let start = center(cx, format!("picker-range-day-{first}"));
+    let middle = center(cx, format!("picker-range-day-{}", first.with_day(3).unwrap()));
+    let end = center(cx, format!("picker-range-day-{}", first.with_day(5).unwrap()));
+    cx.simulate_mouse_down(start, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(middle, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(end, MouseButton::Left, Modifiers::none());
+    cx.run_until_parked();
+    assert_eq!(
+        read_picker(&view, range_picker, cx, |picker| picker.calendar().preview_range()),
+        Some(DateRange::new(first, first.with_day(5).unwrap()))
+    );
+    cx.simulate_mouse_up(end, MouseButton::Left, Modifiers::none());
+    cx.run_until_parked();
+    assert!(!read_picker(&view, range_picker, cx, DatePicker::is_open));
+    assert_eq!(
+        read_picker(&view, range_picker, cx, DatePicker::value),
+        Some(DateValue::Range(DateRange::new(first, first.with_day(5).unwrap())))
+    );
+
+    // 拖到下一月按钮上停留，自动翻月
+    click(cx, "picker-range-trigger");
+    let start = center(cx, format!("picker-range-day-{first}"));
+    let arrow = center(cx, "picker-range-next-month".to_string());
+    cx.simulate_mouse_down(start, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(start + point(px(10.0), px(0.0)), MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(arrow, MouseButton::Left, Modifiers::none());
+    cx.executor().advance_clock(Duration::from_millis(650));
+    cx.run_until_parked();
+    assert_eq!(current_month(&view, range_picker, cx), first.checked_add_months(Months::new(1)).unwrap());
+
+    // 在日期格之外松开，放弃这次拖动，起点与预览都不留下
+    cx.simulate_mouse_up(arrow, MouseButton::Left, Modifiers::none());
+    cx.run_until_parked();
+    assert_eq!(read_picker(&view, range_picker, cx, |picker| picker.calendar().range_anchor), None);
+    assert_eq!(read_picker(&view, range_picker, cx, |picker| picker.calendar().preview_range()), None);
+}
+
+#[gpui::test]
+fn range_picker_drag_released_outside_popup_is_cancelled(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let range_picker = 4;
+    let first = first_of_month(today());
+
+    click(cx, "picker-range-trigger");
+    let start = find(cx, format!("picker-range-day-{first}")).center();
+    let outside = find(cx, "picker-range-mask").bottom_right() - point(px(2.0), px(2.0));
+    cx.simulate_mouse_down(start, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(start + point(px(10.0), px(0.0)), MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(outside, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_up(outside, MouseButton::Left, Modifiers::none());
+    cx.run_until_parked();
+
+    assert!(read_picker(&view, range_picker, cx, DatePicker::is_open));
+    assert_eq!(read_picker(&view, range_picker, cx, |picker| picker.calendar().range_anchor), None);
+    assert_eq!(read_picker(&view, range_picker, cx, |picker| picker.calendar().preview_range()), None);
+}
+
+#[gpui::test]
+fn range_picker_days_show_decorator_elements(cx: &mut TestAppContext) {
+    let (_view, cx) = open_app(cx);
+    let first = first_of_month(today());
+
+    click(cx, "picker-range-trigger");
+    let day = find(cx, format!("picker-range-day-{first}"));
+    let price = try_find(cx, format!("price-{first}")).expect("范围选择器应为每天标出房价");
+    assert!(day.contains(&price.center()));
+}
+
+#[gpui::test]
+fn footer_clear_and_today(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let today = today();
+    assert_eq!(read_picker(&view, 0, cx, DatePicker::caption).as_deref(), Some("今天"));
+
+    click(cx, "picker-0-trigger");
+    click(cx, "picker-0-clear");
+    assert_eq!(read_picker(&view, 0, cx, DatePicker::selected_date), None);
+    assert_eq!(read_picker(&view, 0, cx, DatePicker::caption), None);
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_open));
+
+    click(cx, "picker-0-trigger");
+    click(cx, "picker-0-prev-year");
+    click(cx, "picker-0-today");
+    assert_eq!(read_picker(&view, 0, cx, DatePicker::selected_date), Some(today));
+    assert_eq!(current_month(&view, 0, cx), first_of_month(today));
+}
+
+#[gpui::test]
+fn confirm_mode_commits_only_on_confirm(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let today = today();
+    let target = first_of_month(today).with_day(if today.day() == 15 { 16 } else { 15 }).unwrap();
+
+    // 取消恢复原值
+    click(cx, "picker-2-trigger");
+    click(cx, format!("picker-2-day-{target}"));
+    assert!(read_picker(&view, 2, cx, DatePicker::is_open));
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(target));
+    click(cx, "picker-2-cancel");
+    assert!(!read_picker(&view, 2, cx, DatePicker::is_open));
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(today));
+
+    // 点击遮罩同样放弃暂定选择
+    click(cx, "picker-2-trigger");
+    click(cx, format!("picker-2-day-{target}"));
+    click_mask(cx, "picker-2");
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(today));
+
+    click(cx, "picker-2-trigger");
+    click(cx, format!("picker-2-day-{target}"));
+    click(cx, "picker-2-confirm");
+    assert!(!read_picker(&view, 2, cx, DatePicker::is_open));
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(target));
+}
+
+#[gpui::test]
+fn inline_picker_supports_keyboard(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let idx = 6;
+    let first = first_of_month(today());
+    assert!(try_find(cx, "picker-inline-trigger").is_none());
+
+    click(cx, format!("picker-inline-day-{first}"));
+    assert_eq!(read_picker(&view, idx, cx, DatePicker::selected_date), Some(first));
+    assert!(!read_picker(&view, idx, cx, DatePicker::is_open));
+
+    cx.simulate_keystrokes("right down enter");
+    let expected = first + Days::new(8);
+    assert_eq!(read_picker(&view, idx, cx, DatePicker::selected_date), Some(expected));
+
+    cx.simulate_keystrokes("pagedown");
+    let focused = read_picker(&view, idx, cx, |picker| picker.calendar().focused_date);
+    assert_eq!(focused, Some(expected + Months::new(1)));
+    assert_eq!(current_month(&view, idx, cx), first + Months::new(1));
+
+    cx.simulate_keystrokes("escape");
+    let focused = read_picker(&view, idx, cx, |picker| picker.calendar().focused_date);
+    assert_eq!(focused, None);
+}
+
+#[gpui::test]
+fn scroll_picker_renders_visible_months_and_jumps_to_today(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let idx = 9;
+    let first = first_of_month(today());
+    let next_year = first + Months::new(12);
+    assert!(try_find(cx, "picker-scroll-trigger").is_none());
+    assert!(try_find(cx, "picker-scroll-sticky-month").is_some());
+    assert!(try_find(cx, format!("picker-scroll-day-{next_year}")).is_none());
+
+    click(cx, format!("picker-scroll-day-{first}"));
+    assert_eq!(read_picker(&view, idx, cx, DatePicker::selected_date), Some(first));
+
+    // 光标跨月时把该月滚到顶部
+    cx.simulate_keystrokes("shift-pagedown");
+    assert!(try_find(cx, format!("picker-scroll-day-{next_year}")).is_some());
+    assert!(try_find(cx, format!("picker-scroll-day-{first}")).is_none());
+
+    click(cx, "picker-scroll-jump-today");
+    assert!(try_find(cx, format!("picker-scroll-day-{first}")).is_some());
+    assert_eq!(current_month(&view, idx, cx), first);
+}
+
+fn right_click(cx: &mut VisualTestContext, selector: impl Into<String>) {
+    let bounds = find(cx, selector);
+    cx.simulate_mouse_down(bounds.center(), MouseButton::Right, Modifiers::none());
+    cx.simulate_mouse_up(bounds.center(), MouseButton::Right, Modifiers::none());
+    cx.run_until_parked();
+}
+
+fn clipboard_text(cx: &mut VisualTestContext) -> Option<String> {
+    cx.update(|_, cx| cx.read_from_clipboard().and_then(|item| item.text()))
+}
+
+#[gpui::test]
+fn day_context_menu_runs_builtin_and_registered_items(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let inline = 6;
+    let first = first_of_month(today());
+    let tenth = first.with_day(10).unwrap();
+
+    right_click(cx, format!("picker-inline-day-{tenth}"));
+    assert_eq!(read_picker(&view, inline, cx, DatePicker::context_menu_date), Some(tenth));
+    // 单选模式没有「选中整周」
+    assert!(try_find(cx, "picker-inline-menu-select-week").is_none());
+    click(cx, "picker-inline-menu-copy-date");
+    assert_eq!(clipboard_text(cx), Some(tenth.to_string()));
+    assert_eq!(read_picker(&view, inline, cx, DatePicker::context_menu_date), None);
+
+    // 应用登记的项
+    right_click(cx, format!("picker-inline-day-{tenth}"));
+    click(cx, "picker-inline-menu-item-0");
+    assert_eq!(clipboard_text(cx), Some(format!("{}年{}月10日", first.year(), first.month())));
+
+    // 点击菜单外的遮罩只关闭菜单
+    right_click(cx, format!("picker-inline-day-{tenth}"));
+    click_mask(cx, "picker-inline-menu");
+    assert_eq!(read_picker(&view, inline, cx, DatePicker::context_menu_date), None);
+    assert_eq!(read_picker(&view, inline, cx, DatePicker::selected_date), Some(today()));
+
+    // 范围选择器的弹层中选中整周，网格每行从周日开始
+    let range_picker = 4;
+    click(cx, "picker-range-trigger");
+    right_click(cx, format!("picker-range-day-{tenth}"));
+    click(cx, "picker-range-menu-select-week");
+    let sunday = tenth - Days::new(tenth.weekday().num_days_from_sunday() as u64);
+    assert_eq!(
+        read_picker(&view, range_picker, cx, DatePicker::value),
+        Some(DateValue::Range(DateRange::new(sunday, sunday + Days::new(6))))
+    );
+    assert!(!read_picker(&view, range_picker, cx, DatePicker::is_open));
+}
+
+#[gpui::test]
+fn enter_selects_like_a_click_in_week_mode(cx: &mut TestAppContext) {
+    cx.update(gpui_demo::init);
+    let third = first_of_month(today()).with_day(3).unwrap();
+    let disabled = third + Days::new(7);
+    let (picker, cx) = cx.add_window_view(|_, cx| {
+        DatePicker::new(cx)
+            .clock(Clock::Fixed(today()))
+            .id("picker-rules")
+            .display_mode(DisplayMode::Inline)
+            .selection_mode(SelectionMode::Week)
+            .rules(DateRules::default().disable_dates([disabled]))
+    });
+    cx.run_until_parked();
+
+    // 光标落在禁用的日期上，但这一周还有可选的日子，与点击一样选中整周
+    click(cx, format!("picker-rules-day-{third}"));
+    cx.simulate_keystrokes("down enter");
+    assert_eq!(
+        picker.read_with(cx, |picker, _| picker.value()),
+        Some(DateValue::Week(Week::containing(disabled)))
+    );
+}
+
+#[gpui::test]
+fn disabled_days_have_no_context_menu(cx: &mut TestAppContext) {
+    cx.update(gpui_demo::init);
+    let tenth = first_of_month(today()).with_day(10).unwrap();
+    let eleventh = tenth.succ_opt().unwrap();
+    let (picker, cx) = cx.add_window_view(|_, cx| {
+        DatePicker::new(cx)
+            .clock(Clock::Fixed(today()))
+            .id("picker-rules")
+            .display_mode(DisplayMode::Inline)
+            .rules(DateRules::default().disable_dates([tenth]))
+    });
+    cx.run_until_parked();
+
+    // 禁用的日期不能在这天新建日程
+    right_click(cx, format!("picker-rules-day-{tenth}"));
+    assert_eq!(picker.read_with(cx, |picker, _| picker.context_menu_date()), None);
+    assert!(try_find(cx, "picker-rules-menu-new-event").is_none());
+
+    right_click(cx, format!("picker-rules-day-{eleventh}"));
+    assert_eq!(picker.read_with(cx, |picker, _| picker.context_menu_date()), Some(eleventh));
+}
+
+#[gpui::test]
+fn copy_and_paste_use_the_clipboard(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let inline = 6;
+    let first = first_of_month(today());
+
+    click(cx, format!("picker-inline-day-{first}"));
+    cx.simulate_keystrokes("secondary-c");
+    let item = cx.update(|_, cx| cx.read_from_clipboard()).expect("应写入剪贴板");
+    assert_eq!(item.text(), Some(first.to_string()));
+    assert_eq!(item.metadata(), Some(&first.to_string()));
+
+    let pasted = first + Days::new(9);
+    cx.update(|_, cx| cx.write_to_clipboard(ClipboardItem::new_string(format!(" {pasted} "))));
+    cx.simulate_keystrokes("secondary-v");
+    assert_eq!(read_picker(&view, inline, cx, DatePicker::selected_date), Some(pasted));
+
+    // 无法解析的文本被忽略
+    cx.update(|_, cx| cx.write_to_clipboard(ClipboardItem::new_string("不是日期".to_string())));
+    cx.simulate_keystrokes("secondary-v");
+    assert_eq!(read_picker(&view, inline, cx, DatePicker::selected_date), Some(pasted));
+}
+
+#[gpui::test]
+fn paste_commits_in_confirm_mode(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let today = today();
+    let target = first_of_month(today).with_day(if today.day() == 15 { 16 } else { 15 }).unwrap();
+    let pasted = first_of_month(today).with_day(20).unwrap();
+    let picker = view.read_with(cx, |app, _| app.pickers[2].clone());
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    cx.update(|_, cx| {
+        let changes = changes.clone();
+        cx.subscribe(&picker, move |_, event: &DatePickerEvent, _| {
+            if let DatePickerEvent::Changed(value) = event {
+                changes.borrow_mut().push(*value);
+            }
+        })
+        .detach();
+    });
+
+    // 暂定选择之后粘贴，直接提交并通知
+    click(cx, "picker-2-trigger");
+    click(cx, format!("picker-2-day-{target}"));
+    picker.update(cx, |picker, cx| picker.paste(&pasted.to_string(), cx)).unwrap();
+    cx.run_until_parked();
+    assert!(!read_picker(&view, 2, cx, DatePicker::is_open));
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(pasted));
+    assert_eq!(*changes.borrow(), vec![Some(DateValue::Date(pasted))]);
+
+    // 取消不会恢复到粘贴之前的值
+    click(cx, "picker-2-trigger");
+    click(cx, "picker-2-cancel");
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(pasted));
+
+    // 再次粘贴相同的值，取值没有变化，不再通知
+    picker.update(cx, |picker, cx| picker.paste(&pasted.to_string(), cx)).unwrap();
+    cx.run_until_parked();
+    assert_eq!(read_picker(&view, 2, cx, DatePicker::selected_date), Some(pasted));
+    assert_eq!(*changes.borrow(), vec![Some(DateValue::Date(pasted))]);
+}
+
+#[gpui::test]
+fn arrow_keys_open_popup_and_escape_cancels(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    click(cx, "picker-0-trigger");
+    cx.simulate_keystrokes("escape");
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_open));
+
+    cx.simulate_keystrokes("down");
+    assert!(read_picker(&view, 0, cx, DatePicker::is_open));
+
+    // Esc 只收起弹层，键盘光标移动不改变取值
+    cx.simulate_keystrokes("down escape");
+    assert!(!read_picker(&view, 0, cx, DatePicker::is_open));
+    assert_eq!(read_picker(&view, 0, cx, DatePicker::selected_date), Some(today()));
+}
+
+#[gpui::test]
+fn month_picker_pages_and_respects_rules(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let year = today().year();
+    let month_picker = view.read_with(cx, |app, _| app.month_picker.clone());
+
+    click(cx, "picker-month-trigger");
+    assert!(month_picker.read_with(cx, |picker, _| picker.is_open()));
+    click(cx, "picker-month-prev-page");
+    click(cx, format!("picker-month-period-{}-01", year - 1));
+    assert_eq!(month_picker.read_with(cx, |picker, _| picker.value()), YearMonth::new(year - 1, 1));
+    assert!(!month_picker.read_with(cx, |picker, _| picker.is_open()));
+
+    // 未来的月份不可选，点击后弹层保持打开、取值不变
+    click(cx, "picker-month-trigger");
+    click(cx, "picker-month-next-page");
+    click(cx, "picker-month-next-page");
+    click(cx, format!("picker-month-period-{}-01", year + 1));
+    assert!(month_picker.read_with(cx, |picker, _| picker.is_open()));
+    assert_eq!(month_picker.read_with(cx, |picker, _| picker.value()), YearMonth::new(year - 1, 1));
+}
+
+#[gpui::test]
+fn week_picker_selects_whole_row(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let idx = 7;
+    let first = first_of_month(today());
+
+    click(cx, "picker-week-trigger");
+    click(cx, format!("picker-week-day-{first}"));
+    assert_eq!(
+        read_picker(&view, idx, cx, DatePicker::value),
+        Some(DateValue::Week(Week::containing(first)))
+    );
+    assert!(!read_picker(&view, idx, cx, DatePicker::is_open));
+    assert_eq!(
+        read_picker(&view, idx, cx, DatePicker::format_date),
+        Week::containing(first).to_string()
+    );
+}
+
+#[gpui::test]
+fn zone_picker_uses_zone_today(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let idx = 8;
+    let today = Utc::now().with_timezone(&Tz::America__New_York).date_naive();
+
+    click(cx, "picker-zone-trigger");
+    click(cx, "picker-zone-today");
+    assert_eq!(read_picker(&view, idx, cx, DatePicker::selected_date), Some(today));
+    let abbreviation = read_picker(&view, idx, cx, DatePicker::zone_abbreviation);
+    assert!(matches!(abbreviation.as_deref(), Some("EST" | "EDT")));
+
+    let (start, end) = read_picker(&view, idx, cx, DatePicker::value_instants).unwrap();
+    assert_eq!(start.date_naive(), today);
+    assert!((end - start).num_hours() >= 23);
+}
+
+#[gpui::test]
+fn rendered_pickers_register_their_clocks(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let clocks = cx.update(|_, cx| DayRollover::clocks(cx));
+    assert!(clocks.contains(&Clock::Fixed(today())));
+    assert!(clocks.contains(&Clock::Zone(Tz::America__New_York)));
+
+    // 运行时更换时区后按新时钟重新定时
+    let picker = view.read_with(cx, |app, _| app.pickers[0].clone());
+    picker.update(cx, |picker, cx| picker.set_clock(Clock::Zone(Tz::Asia__Tokyo), cx));
+    cx.run_until_parked();
+    assert!(cx.update(|_, cx| DayRollover::clocks(cx)).contains(&Clock::Zone(Tz::Asia__Tokyo)));
+
+    // 定时任务醒来核对后继续等待，不会反复重绘
+    cx.executor().advance_clock(Duration::from_secs(2 * 60 * 60));
+    cx.run_until_parked();
+    assert!(read_picker(&view, 0, cx, |picker| picker.calendar().clock == Clock::Zone(Tz::Asia__Tokyo)));
+}
+
+#[gpui::test]
+fn released_pickers_unregister_their_clocks(cx: &mut TestAppContext) {
+    cx.update(gpui_demo::init);
+    let zone = Clock::Zone(Tz::Pacific__Auckland);
+    let (picker, window_cx) = cx.add_window_view(|_, cx| DatePicker::new(cx).clock(zone));
+    window_cx.run_until_parked();
+    assert!(window_cx.update(|_, cx| DayRollover::clocks(cx)).contains(&zone));
+
+
+    // 窗口关闭、选择器释放后注销它的时钟
+    window_cx.update(|window, _| window.remove_window());
+    drop(picker);
+    cx.update(|_| {});
+    assert!(!cx.update(|cx| DayRollover::clocks(cx)).contains(&zone));
+}
+
+#[gpui::test]
+fn closed_windows_stop_being_watched_for_appearance(cx: &mut TestAppContext) {
+    cx.update(gpui_demo::init);
+    let (_picker, window_cx) = cx.add_window_view(|_, cx| DatePicker::new(cx).clock(Clock::Fixed(today())));
+    window_cx.run_until_parked();
+    let id = window_cx.update(|window, _| window.window_handle().window_id());
+    assert!(window_cx.update(|_, cx| ThemeState::watched_windows(cx)).contains(&id));
+
+    window_cx.update(|window, _| window.remove_window());
+    cx.update(|_| {});
+    assert!(!cx.update(|cx| ThemeState::watched_windows(cx)).contains(&id));
+}
+
+#[gpui::test]
+fn year_view_opens_clicked_month_in_picker(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let year_view = view.read_with(cx, |app, _| app.year_view.clone());
+    let picker = year_view.read_with(cx, |year_view, _| year_view.picker().clone());
+    let today = today();
+    let target = NaiveDate::from_ymd_opt(today.year(), 2, 10).unwrap();
+
+    // 点击只在弹层中打开该月，选择在弹层中完成后年视图才标出
+    click(cx, format!("year-view-day-{target}"));
+    assert!(picker.read_with(cx, |picker, _| picker.is_open()));
+    assert_eq!(picker.read_with(cx, |picker, _| picker.calendar().current_month), first_of_month(target));
+    assert_eq!(year_view.read_with(cx, |year_view, _| year_view.selected_date()), Some(today));
+
+    click(cx, format!("picker-year-view-day-{target}"));
+    assert!(!picker.read_with(cx, |picker, _| picker.is_open()));
+    assert_eq!(year_view.read_with(cx, |year_view, _| year_view.selected_date()), Some(target));
+
+    click(cx, "year-view-prev-year");
+    let last_year = target - Months::new(12);
+    let shown_year = year_view.read_with(cx, |year_view, _| year_view.calendar().current_month.year());
+    assert_eq!(shown_year, last_year.year());
+    assert!(try_find(cx, format!("year-view-day-{last_year}")).is_some());
+    assert!(try_find(cx, format!("year-view-day-{target}")).is_none());
+}
+
+#[gpui::test]
+fn year_view_follows_picker_settings(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let year_view = view.read_with(cx, |app, _| app.year_view.clone());
+    let picker = year_view.read_with(cx, |year_view, _| year_view.picker().clone());
+    let shown_year = year_view.read_with(cx, |year_view, _| year_view.calendar().current_month);
+
+    picker.update(cx, |picker, cx| picker.set_clock(Clock::Zone(Tz::Asia__Tokyo), cx));
+    cx.run_until_parked();
+    let calendar = year_view.read_with(cx, |year_view, _| year_view.calendar().clone());
+    assert_eq!(calendar.clock, Clock::Zone(Tz::Asia__Tokyo));
+    assert_eq!(calendar.current_month, shown_year);
+}
+
+#[gpui::test]
+fn day_cells_show_tooltips_only_when_they_have_items(cx: &mut TestAppContext) {
+    let (_view, cx) = open_app(cx);
+    let today = today();
+    let first = first_of_month(today);
+    let monday = first + Days::new(((7 - first.weekday().days_since(Weekday::Mon)) % 7) as u64);
+    // 示例把今天之后第 3、4 天标为年假，找一个既不是周一也不是假期的日子
+    let leave_days = [3, 4].map(|days| today + Days::new(days));
+    let plain = [1, 2, 3]
+        .map(|days| monday + Days::new(days))
+        .into_iter()
+        .find(|date| !leave_days.contains(date))
+        .unwrap();
+    let hover = |cx: &mut VisualTestContext, date: NaiveDate| {
+        let bounds = find(cx, format!("picker-year-view-day-{date}"));
+        cx.simulate_mouse_move(bounds.center(), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+    };
+
+    click(cx, "picker-year-view-trigger");
+    hover(cx, monday);
+    assert!(try_find(cx, "picker-year-view-tooltip").is_some());
+
+    hover(cx, plain);
+    assert!(try_find(cx, "picker-year-view-tooltip").is_none());
+}
+
+#[gpui::test]
+fn heatmap_cells_select_like_a_date_picker(cx: &mut TestAppContext) {
+    let (view, cx) = open_app(cx);
+    let heatmap = view.read_with(cx, |app, _| app.heatmap.clone());
+    let today = today();
+    assert!(try_find(cx, format!("heatmap-day-{}", today + Days::new(1))).is_none());
+    assert_eq!(heatmap.read_with(cx, |heatmap, _| heatmap.value()), None);
+
+    let last_month = today - Months::new(1);
+    click(cx, format!("heatmap-day-{last_month}"));
+    assert_eq!(heatmap.read_with(cx, |heatmap, _| heatmap.value()), Some(DateValue::Date(last_month)));
+}
@@ -0,0 +1,848 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use gpui_demo::{
+    Calendar, Clock, CompareMode, CompareTarget, DateFormat, DateRange, DateRules, DateValue, GridCache, GridOptions,
+    HEATMAP_WEEKS, HeatmapData, HeatmapLayout, HeatmapScale, Locale, MonthList, ParseDateError, ParseHeatmapError,
+    Period, PeriodCalendar, Quarter, RangePreset, RelativeStyle, SelectionMode, Tz, Week, WorkingDays, Year, YearMonth,
+    convert_start_of_day, date_in_zone, days_in_month, describe_relative, describe_relative_range, heatmap_tooltip,
+    range_instants, start_of_day, zone_abbreviation,
+};
+use proptest::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+enum Nav {
+    PrevMonth,
+    NextMonth,
+    PrevYear,
+    NextYear,
+}
+
+fn calendar_at(date: NaiveDate) -> Calendar {
+    Calendar {
+        current_month: date,
+        selected_date: None,
+        ..Calendar::new()
+    }
+}
+
+fn date_in_range() -> impl Strategy<Value = NaiveDate> {
+    let min = NaiveDate::MIN.num_days_from_ce();
+    let max = NaiveDate::MAX.num_days_from_ce();
+    // 偏向范围两端，越界处理才会被经常覆盖
+    prop_oneof![
+        min..=max,
+        min..=min + 800,
+        max - 800..=max,
+    ]
+    .prop_map(|days| NaiveDate::from_num_days_from_ce_opt(days).unwrap())
+}
+
+fn nav() -> impl Strategy<Value = Nav> {
+    prop_oneof![
+        Just(Nav::PrevMonth),
+        Just(Nav::NextMonth),
+        Just(Nav::PrevYear),
+        Just(Nav::NextYear),
+    ]
+}
+
+fn apply(calendar: &mut Calendar, nav: Nav) {
+    match nav {
+        Nav::PrevMonth => calendar.prev_month(),
+        Nav::NextMonth => calendar.next_month(),
+        Nav::PrevYear => calendar.prev_year(),
+        Nav::NextYear => calendar.next_year(),
+    }
+}
+
+fn check_grid(calendar: &Calendar) {
+    let days = calendar.get_month_days();
+    assert_eq!(days.len(), 42);
+
+    let month = calendar.current_month;
+    let first_day = month.with_day(1).unwrap();
+    let leading = first_day.weekday().num_days_from_sunday() as usize;
+    assert_eq!(days[leading], Some(first_day));
+
+    let in_month: Vec<_> = days
+        .iter()
+        .flatten()
+        .filter(|date| calendar.is_current_month(date))
+        .collect();
+    assert_eq!(in_month.len() as u32, days_in_month(month));
+
+    for (idx, date) in days.iter().enumerate() {
+        if let Some(date) = date {
+            assert_eq!(date.weekday().num_days_from_sunday() as usize, idx % 7);
+        }
+    }
+    for pair in days.windows(2) {
+        if let [Some(a), Some(b)] = pair {
+            assert_eq!(a.succ_opt(), Some(*b));
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn navigation_never_panics_and_keeps_grid_invariants(
+        start in date_in_range(),
+        steps in prop::collection::vec(nav(), 0..64),
+    ) {
+        let mut calendar = calendar_at(start);
+        check_grid(&calendar);
+        for step in steps {
+            apply(&mut calendar, step);
+            prop_assert_eq!(calendar.current_month.day(), 1);
+            check_grid(&calendar);
+        }
+    }
+
+    #[test]
+    fn cached_grid_matches_fresh_grid(
+        start in date_in_range(),
+        steps in prop::collection::vec(nav(), 0..32),
+    ) {
+        let mut cache = GridCache::default();
+        let mut calendar = calendar_at(start);
+        for step in steps {
+            apply(&mut calendar, step);
+            calendar.grid_options.show_adjacent_days = !calendar.grid_options.show_adjacent_days;
+            prop_assert_eq!(cache.get(&calendar).to_vec(), calendar.get_month_days());
+        }
+    }
+
+    #[test]
+    fn month_navigation_round_trips_away_from_edges(start in date_in_range()) {
+        let mut calendar = calendar_at(start);
+        calendar.next_month();
+        calendar.prev_month();
+        let first_day = start.with_day(1).unwrap();
+        let at_edge = first_day.year() == NaiveDate::MAX.year() && first_day.month() == 12;
+        if !at_edge {
+            prop_assert_eq!(calendar.current_month, first_day);
+        }
+    }
+
+    #[test]
+    fn trimmed_grid_ends_on_the_last_week_of_the_month(start in date_in_range()) {
+        let mut calendar = calendar_at(start);
+        calendar.grid_options = GridOptions {
+            show_adjacent_days: false,
+            trim_trailing_rows: true,
+            ..Default::default()
+        };
+        let days = calendar.get_month_days();
+        prop_assert!(days.len().is_multiple_of(7) && days.len() <= 42);
+        prop_assert!(days[days.len() - 7..].iter().flatten().any(|date| calendar.is_current_month(date)));
+        prop_assert!(days.iter().flatten().all(|date| calendar.is_current_month(date)));
+    }
+}
+
+#[test]
+fn year_navigation_from_leap_day() {
+    let mut calendar = calendar_at(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    calendar.next_year();
+    assert_eq!(calendar.current_month, NaiveDate::from_ymd_opt(2025, 2, 1).unwrap());
+    calendar.prev_year();
+    calendar.prev_year();
+    assert_eq!(calendar.current_month, NaiveDate::from_ymd_opt(2023, 2, 1).unwrap());
+}
+
+#[test]
+fn navigation_clamps_at_range_edges() {
+    let mut calendar = calendar_at(NaiveDate::MIN);
+    calendar.prev_month();
+    calendar.prev_year();
+    assert_eq!(calendar.current_month, NaiveDate::MIN);
+    check_grid(&calendar);
+
+    let mut calendar = calendar_at(NaiveDate::MAX);
+    calendar.next_month();
+    calendar.next_year();
+    assert_eq!(calendar.current_month, NaiveDate::MAX.with_day(1).unwrap());
+    check_grid(&calendar);
+}
+
+#[test]
+fn selecting_an_adjacent_day_moves_to_its_month() {
+    let mut calendar = calendar_at(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+    let first_cell = calendar.get_month_days()[0].unwrap();
+    assert_eq!(first_cell, NaiveDate::from_ymd_opt(2025, 2, 23).unwrap());
+    assert_eq!(first_cell.weekday(), Weekday::Sun);
+
+    calendar.select_date(first_cell);
+    assert_eq!(calendar.selected_date, Some(first_cell));
+    assert_eq!(calendar.current_month, NaiveDate::from_ymd_opt(2025, 2, 1).unwrap());
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn presets_resolve_against_the_clock() {
+    let today = ymd(2025, 5, 14);
+    let expected = [
+        (RangePreset::Today, ymd(2025, 5, 14), ymd(2025, 5, 14)),
+        (RangePreset::Yesterday, ymd(2025, 5, 13), ymd(2025, 5, 13)),
+        (RangePreset::Last7Days, ymd(2025, 5, 8), ymd(2025, 5, 14)),
+        (RangePreset::Last30Days, ymd(2025, 4, 15), ymd(2025, 5, 14)),
+        (RangePreset::ThisMonth, ymd(2025, 5, 1), ymd(2025, 5, 14)),
+        (RangePreset::LastMonth, ymd(2025, 4, 1), ymd(2025, 4, 30)),
+        (RangePreset::ThisQuarter, ymd(2025, 4, 1), ymd(2025, 5, 14)),
+        (RangePreset::YearToDate, ymd(2025, 1, 1), ymd(2025, 5, 14)),
+    ];
+    for (preset, start, end) in expected {
+        assert_eq!(preset.resolve(today), DateRange { start, end }, "{preset:?}");
+    }
+}
+
+#[test]
+fn active_preset_follows_the_selected_range() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.selection_mode = SelectionMode::Range;
+    calendar.apply_preset(RangePreset::Last30Days);
+    assert_eq!(calendar.active_preset(), Some(RangePreset::Last30Days));
+    assert_eq!(calendar.current_month, ymd(2025, 4, 1));
+
+    calendar.pick(ymd(2025, 5, 8));
+    calendar.pick(ymd(2025, 5, 14));
+    assert_eq!(calendar.active_preset(), Some(RangePreset::Last7Days));
+
+    calendar.pick(ymd(2025, 5, 2));
+    calendar.pick(ymd(2025, 5, 3));
+    assert_eq!(calendar.active_preset(), None);
+}
+
+#[test]
+fn range_pick_needs_two_clicks_in_any_order() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.selection_mode = SelectionMode::Range;
+    assert_eq!(calendar.value(), None);
+
+    assert!(!calendar.pick(ymd(2025, 5, 20)));
+    assert!(calendar.is_selected(&ymd(2025, 5, 20)));
+    assert_eq!(calendar.value(), None);
+
+    assert!(calendar.pick(ymd(2025, 5, 10)));
+    let range = DateRange::new(ymd(2025, 5, 10), ymd(2025, 5, 20));
+    assert_eq!(calendar.value(), Some(DateValue::Range(range)));
+    assert!(calendar.is_in_range(&ymd(2025, 5, 15)));
+    assert_eq!(range.num_days(), 11);
+}
+
+#[test]
+fn values_round_trip_through_text() {
+    let format = DateFormat::new("%Y/%m/%d");
+    let range = DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 7));
+    assert_eq!(format.format_value(&DateValue::Range(range)), "2025/03/01 ~ 2025/03/07");
+    assert_eq!(DateValue::Range(range).to_iso_string(), "2025-03-01/2025-03-07");
+    assert_eq!(DateValue::Date(ymd(2025, 3, 14)).to_iso_string(), "2025-03-14");
+    assert_eq!(DateValue::Week(Week::containing(ymd(2025, 2, 26))).to_iso_string(), "2025-W09");
+
+    let parse = |text: &str, mode| format.parse_value(text, mode);
+    assert_eq!(parse(" 2025/03/14 ", SelectionMode::Single), Ok(DateValue::Date(ymd(2025, 3, 14))));
+    assert_eq!(parse("2025/03/01 ~ 2025/03/07", SelectionMode::Range), Ok(DateValue::Range(range)));
+    assert_eq!(parse("2025-03-07/2025-03-01", SelectionMode::Range), Ok(DateValue::Range(range)));
+    assert_eq!(
+        parse("2025/03/14", SelectionMode::Comparison),
+        Ok(DateValue::Range(DateRange::single(ymd(2025, 3, 14))))
+    );
+    let week = DateValue::Week(Week::containing(ymd(2025, 2, 24)));
+    assert_eq!(parse("2025-W09", SelectionMode::Week), Ok(week));
+    assert_eq!(parse("2025/03/02", SelectionMode::Week), Ok(week));
+    assert_eq!(
+        parse("明天", SelectionMode::Single),
+        Err(ParseDateError::Invalid("明天".to_string()))
+    );
+    assert_eq!(parse("2025/03/01 ~", SelectionMode::Range), Err(ParseDateError::Empty));
+}
+
+#[test]
+fn comparison_values_survive_copy_and_paste() {
+    let format = DateFormat::new("%Y/%m/%d");
+    let primary = DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 7));
+    let compare = DateRange::new(ymd(2025, 1, 10), ymd(2025, 1, 16));
+    let value = DateValue::Comparison { primary, compare };
+    assert_eq!(value.to_iso_string(), "2025-03-01/2025-03-07,2025-01-10/2025-01-16");
+
+    for text in [value.to_iso_string(), format.format_value(&value)] {
+        let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+        calendar.selection_mode = SelectionMode::Comparison;
+        assert_eq!(format.parse_value(&text, SelectionMode::Comparison), Ok(value));
+        assert_eq!(calendar.paste(&text, &format), Ok(true));
+        assert_eq!(calendar.value(), Some(value));
+        assert_eq!(calendar.compare_mode, CompareMode::Custom);
+    }
+
+    // 与推导结果一致时保留原来的对比方式
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.selection_mode = SelectionMode::Comparison;
+    let derived = DateValue::Comparison {
+        primary,
+        compare: CompareMode::PreviousPeriod.derive(primary).unwrap(),
+    };
+    assert_eq!(calendar.paste(&derived.to_iso_string(), &format), Ok(true));
+    assert_eq!(calendar.compare_mode, CompareMode::PreviousPeriod);
+    assert_eq!(calendar.value(), Some(derived));
+}
+
+#[test]
+fn paste_selects_only_allowed_values() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.rules = DateRules::default().max_date(ymd(2025, 5, 31));
+    let format = DateFormat::ISO;
+
+    assert_eq!(calendar.paste("2025-03-14", &format), Ok(true));
+    assert_eq!(calendar.value(), Some(DateValue::Date(ymd(2025, 3, 14))));
+    assert_eq!(calendar.current_month, ymd(2025, 3, 1));
+    assert_eq!(
+        calendar.paste("2025-06-01", &format),
+        Err(ParseDateError::NotAllowed("2025-06-01".to_string()))
+    );
+    assert_eq!(calendar.value(), Some(DateValue::Date(ymd(2025, 3, 14))));
+
+    calendar.selection_mode = SelectionMode::Range;
+    assert!(calendar.paste("2025-05-30 ~ 2025-06-02", &format).is_err());
+    assert_eq!(calendar.paste("2025-05-01/2025-05-07", &format), Ok(true));
+    assert_eq!(
+        calendar.value(),
+        Some(DateValue::Range(DateRange::new(ymd(2025, 5, 1), ymd(2025, 5, 7))))
+    );
+}
+
+#[test]
+fn select_week_follows_the_grid_row() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    assert!(!calendar.select_week(ymd(2025, 5, 14)));
+    assert_eq!(calendar.value(), Some(DateValue::Date(ymd(2025, 5, 14))));
+
+    calendar.selection_mode = SelectionMode::Range;
+    assert!(calendar.select_week(ymd(2025, 5, 14)));
+    assert_eq!(calendar.value(), Some(DateValue::Range(DateRange::new(ymd(2025, 5, 11), ymd(2025, 5, 17)))));
+
+    calendar.grid_options.week_start = Weekday::Mon;
+    assert!(calendar.select_week(ymd(2025, 5, 14)));
+    assert_eq!(calendar.value(), Some(DateValue::Range(DateRange::new(ymd(2025, 5, 12), ymd(2025, 5, 18)))));
+}
+
+#[test]
+fn select_week_respects_rules() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.selection_mode = SelectionMode::Range;
+    calendar.rules = DateRules::default().disable_weekdays([Weekday::Sat, Weekday::Sun]);
+
+    // 2025-05-11 至 05-17 的两端是周日、周六，收缩到周一至周五
+    assert!(calendar.select_week(ymd(2025, 5, 14)));
+    let value = calendar.value().unwrap();
+    assert_eq!(value, DateValue::Range(DateRange::new(ymd(2025, 5, 12), ymd(2025, 5, 16))));
+    assert!(calendar.rules.allows_value(&value));
+
+    // 整行都不可选时不改动已有的选择
+    calendar.rules = DateRules::default().min_date(ymd(2025, 6, 1));
+    assert!(!calendar.select_week(ymd(2025, 5, 14)));
+    assert_eq!(calendar.value(), Some(value));
+
+    calendar.selection_mode = SelectionMode::Week;
+    calendar.selected_date = None;
+    assert!(!calendar.select_week(ymd(2025, 5, 14)));
+    assert_eq!(calendar.value(), None);
+}
+
+#[test]
+fn today_and_presets_respect_rules() {
+    let today = ymd(2025, 5, 14);
+    let mut calendar = Calendar::with_clock(Clock::Fixed(today));
+    calendar.selected_date = Some(ymd(2025, 5, 2));
+    calendar.rules = DateRules::default().disable_dates([today]);
+
+    assert!(!calendar.select_today());
+    assert_eq!(calendar.value(), Some(DateValue::Date(ymd(2025, 5, 2))));
+
+    calendar.selection_mode = SelectionMode::Range;
+    assert!(!calendar.select_today());
+    assert!(!calendar.apply_preset(RangePreset::Last7Days));
+    assert_eq!(calendar.value(), None);
+
+    // 今天之前的快捷范围不受影响
+    assert!(calendar.apply_preset(RangePreset::LastMonth));
+    assert_eq!(calendar.value(), Some(DateValue::Range(DateRange::new(ymd(2025, 4, 1), ymd(2025, 4, 30)))));
+}
+
+#[test]
+fn range_preview_follows_hover_until_the_second_pick() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.selection_mode = SelectionMode::Range;
+    assert_eq!(calendar.preview_range(), None);
+
+    calendar.pick(ymd(2025, 5, 20));
+    assert_eq!(calendar.preview_range(), Some(DateRange::new(ymd(2025, 5, 20), ymd(2025, 5, 20))));
+
+    // 向前拖动时预览范围仍按先后排好
+    calendar.range_hover = Some(ymd(2025, 5, 12));
+    assert_eq!(calendar.preview_range(), Some(DateRange::new(ymd(2025, 5, 12), ymd(2025, 5, 20))));
+    assert!(calendar.is_in_preview(&ymd(2025, 5, 15)));
+    assert!(!calendar.is_in_preview(&ymd(2025, 5, 21)));
+
+    calendar.pick(ymd(2025, 5, 12));
+    assert_eq!(calendar.preview_range(), None);
+    assert!(!calendar.is_in_preview(&ymd(2025, 5, 15)));
+}
+
+#[test]
+fn derived_compare_ranges() {
+    let primary = DateRange::new(ymd(2024, 3, 1), ymd(2024, 3, 7));
+    assert_eq!(
+        primary.previous_period(),
+        Some(DateRange::new(ymd(2024, 2, 23), ymd(2024, 2, 29)))
+    );
+    assert_eq!(
+        DateRange::new(ymd(2024, 2, 1), ymd(2024, 2, 29)).same_period_last_year(),
+        Some(DateRange::new(ymd(2023, 2, 1), ymd(2023, 2, 28)))
+    );
+    assert_eq!(DateRange::single(NaiveDate::MIN).previous_period(), None);
+}
+
+#[test]
+fn comparison_value_needs_both_ranges() {
+    let mut calendar = Calendar::with_clock(Clock::Fixed(ymd(2025, 5, 14)));
+    calendar.selection_mode = SelectionMode::Comparison;
+
+    assert!(calendar.apply_preset(RangePreset::Last7Days));
+    assert_eq!(
+        calendar.value(),
+        Some(DateValue::Comparison {
+            primary: DateRange::new(ymd(2025, 5, 8), ymd(2025, 5, 14)),
+            compare: DateRange::new(ymd(2025, 5, 1), ymd(2025, 5, 7)),
+        })
+    );
+    assert!(calendar.is_in_compare_range(&ymd(2025, 5, 3)));
+    assert!(calendar.is_compare_endpoint(&ymd(2025, 5, 1)));
+
+    // 自定义对比：主范围完成后继续选择对比范围
+    calendar.compare_mode = CompareMode::Custom;
+    assert_eq!(calendar.value(), None);
+    calendar.pick(ymd(2025, 5, 10));
+    assert!(!calendar.pick(ymd(2025, 5, 12)));
+    assert_eq!(calendar.compare_target, CompareTarget::Compare);
+    calendar.pick(ymd(2025, 4, 10));
+    assert!(calendar.pick(ymd(2025, 4, 12)));
+    assert_eq!(
+        calendar.value(),
+        Some(DateValue::Comparison {
+            primary: DateRange::new(ymd(2025, 5, 10), ymd(2025, 5, 12)),
+            compare: DateRange::new(ymd(2025, 4, 10), ymd(2025, 4, 12)),
+        })
+    );
+}
+
+#[test]
+fn keyboard_focus_follows_months() {
+    let mut calendar = calendar_at(ymd(2025, 1, 1));
+    calendar.clock = Clock::Fixed(ymd(2025, 1, 30));
+    assert_eq!(calendar.focus_origin(), ymd(2025, 1, 30));
+
+    // 跨月时跟随翻月
+    calendar.move_focus(7);
+    assert_eq!(calendar.focused_date, Some(ymd(2025, 2, 6)));
+    assert_eq!(calendar.current_month, ymd(2025, 2, 1));
+
+    // 按月移动时日期取目标月份的最后一天
+    calendar.focused_date = Some(ymd(2025, 1, 31));
+    calendar.move_focus_months(1);
+    assert_eq!(calendar.focused_date, Some(ymd(2025, 2, 28)));
+    calendar.move_focus_months(-12);
+    assert_eq!(calendar.focused_date, Some(ymd(2024, 2, 28)));
+    assert_eq!(calendar.current_month, ymd(2024, 2, 1));
+
+    // 范围选择中光标从起点出发，双月视图内不翻月
+    calendar.selection_mode = SelectionMode::Range;
+    calendar.current_month = ymd(2025, 3, 1);
+    calendar.focused_date = None;
+    calendar.pick(ymd(2025, 3, 30));
+    calendar.move_focus(3);
+    assert_eq!(calendar.focused_date, Some(ymd(2025, 4, 2)));
+    assert_eq!(calendar.current_month, ymd(2025, 3, 1));
+}
+
+#[test]
+fn rules_limit_selectable_days() {
+    let rules = DateRules::default()
+        .min_date(ymd(2025, 3, 5))
+        .max_date(ymd(2025, 3, 25))
+        .disable_weekdays([Weekday::Sat, Weekday::Sun])
+        .disable_dates([ymd(2025, 3, 12)]);
+    let mut calendar = calendar_at(ymd(2025, 3, 1));
+    calendar.rules = rules.clone();
+
+    assert!(!calendar.is_selectable(&ymd(2025, 3, 4)));
+    assert!(calendar.is_selectable(&ymd(2025, 3, 5)));
+    assert!(!calendar.is_selectable(&ymd(2025, 3, 8)));
+    assert!(!calendar.is_selectable(&ymd(2025, 3, 12)));
+    assert!(!calendar.is_selectable(&ymd(2025, 3, 26)));
+
+    assert!(rules.allows_any(DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 5))));
+    assert!(!rules.allows_any(DateRange::new(ymd(2025, 3, 8), ymd(2025, 3, 9))));
+    assert!(!rules.allows_any(DateRange::new(ymd(2025, 4, 1), ymd(2025, 4, 30))));
+}
+
+#[test]
+fn periods_cover_their_days() {
+    let month = YearMonth::containing(ymd(2024, 2, 17));
+    assert_eq!(month.to_string(), "2024-02");
+    assert_eq!(month.range(), DateRange::new(ymd(2024, 2, 1), ymd(2024, 2, 29)));
+
+    let quarter = Quarter::containing(ymd(2025, 8, 31));
+    assert_eq!(quarter.to_string(), "2025-Q3");
+    assert_eq!(quarter.range(), DateRange::new(ymd(2025, 7, 1), ymd(2025, 9, 30)));
+    assert_eq!(Quarter::new(2025, 5), None);
+
+    let year = Year::containing(ymd(2025, 6, 1));
+    assert_eq!(year.to_string(), "2025");
+    assert_eq!(year.range().num_days(), 365);
+
+    // 年份一页是一个十年，前后各补一年
+    let page = Year::page(ymd(2025, 6, 1));
+    assert_eq!(page.len(), 12);
+    assert_eq!(page.first().map(|year| year.year()), Some(2019));
+    assert_eq!(page.last().map(|year| year.year()), Some(2030));
+    assert_eq!(Year::page_title(ymd(2025, 6, 1)), "2020-2029");
+    assert_eq!(YearMonth::page(ymd(2025, 6, 1)).len(), 12);
+    assert_eq!(Quarter::page(ymd(2025, 6, 1)).len(), 4);
+
+    // 周期的边界落在 NaiveDate 范围的尽头
+    assert_eq!(Year::containing(NaiveDate::MAX).last_day(), NaiveDate::MAX);
+}
+
+#[test]
+fn period_calendar_pages_and_rules() {
+    let mut months = PeriodCalendar::<YearMonth>::with_clock(Clock::Fixed(ymd(2025, 5, 20)));
+    months.calendar.rules = DateRules::default().max_date(ymd(2025, 5, 20));
+    assert_eq!(months.selected, YearMonth::new(2025, 5));
+    assert_eq!(months.page_title(), "2025年");
+
+    let june = YearMonth::new(2025, 6).unwrap();
+    assert!(!months.is_selectable(june));
+    assert!(!months.select(june));
+    assert_eq!(months.selected, YearMonth::new(2025, 5));
+
+    months.prev_page();
+    assert_eq!(months.page_title(), "2024年");
+    assert!(months.select(YearMonth::new(2024, 12).unwrap()));
+
+    let mut years = PeriodCalendar::<Year>::with_clock(Clock::Fixed(ymd(2025, 5, 20)));
+    years.next_page();
+    assert_eq!(years.page_title(), "2030-2039");
+    // 选中相邻页补出来的年份时翻到该页
+    assert!(years.select(Year::new(2040).unwrap()));
+    assert_eq!(years.page_title(), "2040-2049");
+}
+
+#[test]
+fn iso_weeks_cross_year_boundaries() {
+    let week = Week::containing(ymd(2024, 12, 30));
+    assert_eq!((week.year(), week.week()), (2025, 1));
+    assert_eq!(week.to_string(), "2025-W01");
+
+    let week = Week::containing(ymd(2021, 1, 3));
+    assert_eq!(week.to_string(), "2020-W53");
+    assert_eq!(week.range(), DateRange::new(ymd(2020, 12, 28), ymd(2021, 1, 3)));
+
+    assert_eq!(Week::new(2025, 9).map(|week| week.to_string()), Some("2025-W09".to_string()));
+    assert_eq!(Week::new(2025, 53), None);
+}
+
+#[test]
+fn week_mode_rows_are_iso_weeks() {
+    let mut calendar = calendar_at(ymd(2025, 1, 1));
+    calendar.selection_mode = SelectionMode::Week;
+    let days = calendar.get_month_days();
+    assert_eq!(days[0], Some(ymd(2024, 12, 30)));
+    for row in days.chunks(7) {
+        let weeks: Vec<_> = row.iter().flatten().map(|date| Week::containing(*date)).collect();
+        assert!(weeks.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    // 点击上个月的日期选中整周，不翻月
+    assert!(calendar.pick(ymd(2024, 12, 31)));
+    assert_eq!(calendar.current_month, ymd(2025, 1, 1));
+    assert_eq!(calendar.value(), Some(DateValue::Week(Week::new(2025, 1).unwrap())));
+    assert!(calendar.is_selected(&ymd(2025, 1, 5)));
+    assert!(!calendar.is_selected(&ymd(2025, 1, 6)));
+    assert!(!calendar.is_in_range(&ymd(2025, 1, 1)));
+}
+
+#[test]
+fn grid_starts_on_configured_weekday() {
+    let mut calendar = calendar_at(ymd(2025, 3, 1));
+    calendar.grid_options.week_start = Weekday::Mon;
+    let days = calendar.get_month_days();
+    assert_eq!(days[0], Some(ymd(2025, 2, 24)));
+    assert_eq!(days[5], Some(ymd(2025, 3, 1)));
+}
+
+#[test]
+fn grid_cache_keys_on_month_and_effective_week_start() {
+    let mut cache = GridCache::default();
+    let mut calendar = calendar_at(ymd(2025, 3, 1));
+    let first = cache.get(&calendar);
+    calendar.selected_date = Some(ymd(2025, 3, 14));
+    assert!(std::rc::Rc::ptr_eq(&first, &cache.get(&calendar)));
+    assert_eq!(cache.len(), 1);
+
+    // 选周模式固定从周一开始，与配置的周日起始是不同的网格
+    calendar.selection_mode = SelectionMode::Week;
+    assert_eq!(cache.get(&calendar)[0], Some(ymd(2025, 2, 24)));
+    calendar.grid_options.week_start = Weekday::Mon;
+    calendar.selection_mode = SelectionMode::Single;
+    cache.get(&calendar);
+    assert_eq!(cache.len(), 2);
+
+    calendar.next_month();
+    assert_eq!(cache.get(&calendar)[0], Some(ymd(2025, 3, 31)));
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn month_list_spans_both_directions_within_rules() {
+    let anchor = ymd(2025, 3, 14);
+    let months = MonthList::around(anchor, &DateRules::default());
+    assert_eq!(months.len(), 2 * MonthList::SPAN as usize + 1);
+    let ix = months.index_of(anchor).unwrap();
+    assert_eq!(ix, MonthList::SPAN as usize);
+    assert_eq!(months.month(ix), ymd(2025, 3, 1));
+    assert_eq!(months.month(ix + 10), ymd(2026, 1, 1));
+    assert_eq!(months.index_of(ymd(1900, 1, 1)), None);
+
+    let rules = DateRules::default().min_date(ymd(2024, 11, 20)).max_date(ymd(2025, 2, 3));
+    let months = MonthList::around(anchor, &rules);
+    assert_eq!(months.len(), 4);
+    assert_eq!(months.month(0), ymd(2024, 11, 1));
+    assert_eq!(months.index_of(ymd(2025, 2, 28)), Some(3));
+    assert_eq!(months.index_of(anchor), None);
+
+    let edge = MonthList::around(NaiveDate::MAX, &DateRules::default());
+    assert_eq!(edge.index_of(NaiveDate::MAX), Some(edge.len() - 1));
+}
+
+#[test]
+fn year_months_and_month_views() {
+    let mut calendar = calendar_at(ymd(2025, 7, 1));
+    calendar.selected_date = Some(ymd(2025, 7, 4));
+    let months = calendar.year_months();
+    assert_eq!(months[0], ymd(2025, 1, 1));
+    assert_eq!(months[11], ymd(2025, 12, 1));
+
+    let february = calendar.month_view(ymd(2025, 2, 14));
+    assert_eq!(february.current_month, ymd(2025, 2, 1));
+    assert_eq!(february.selected_date, calendar.selected_date);
+    assert!(february.get_month_days().iter().flatten().all(|date| date.month() == 2));
+}
+
+#[test]
+fn heatmap_data_from_csv_and_json() {
+    let csv = "date,count\n2025-03-14,3\n\n# 备注\n\"2025-03-14\",2\n2025-03-15,0.5\n";
+    let data = HeatmapData::from_csv(csv, &DateFormat::ISO).unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data.get(&ymd(2025, 3, 14)), Some(5.0));
+    assert_eq!(data.get(&ymd(2025, 3, 15)), Some(0.5));
+    assert_eq!(data.get(&ymd(2025, 3, 16)), None);
+    assert!(matches!(
+        HeatmapData::from_csv("2025-03-14,3\n2025-13-01,1", &DateFormat::ISO),
+        Err(ParseHeatmapError::Date { line: 2, .. })
+    ));
+    // 表头前可以有空行与注释，表头之后不是日期的行仍是错误
+    let data = HeatmapData::from_csv("\n# 导出\n  date,count\n2025-03-14,3\n", &DateFormat::ISO).unwrap();
+    assert_eq!(data.get(&ymd(2025, 3, 14)), Some(3.0));
+    assert!(matches!(
+        HeatmapData::from_csv("\ndate,count\nday,count\n", &DateFormat::ISO),
+        Err(ParseHeatmapError::Date { line: 3, .. })
+    ));
+    assert_eq!(
+        HeatmapData::from_csv("2025-03-14,many", &DateFormat::ISO),
+        Err(ParseHeatmapError::Value { line: 1, text: "many".to_string() })
+    );
+
+    let object = HeatmapData::from_json(r#"{"2025-03-14": 3, "2025-03-15": 1}"#, &DateFormat::ISO).unwrap();
+    let array = HeatmapData::from_json(
+        r#"[{"date": "2025-03-14", "value": 3}, {"date": "2025-03-15", "value": 1}]"#,
+        &DateFormat::ISO,
+    )
+    .unwrap();
+    assert_eq!(object, array);
+    assert_eq!(object.get(&ymd(2025, 3, 14)), Some(3.0));
+    assert!(matches!(HeatmapData::from_json("[1", &DateFormat::ISO), Err(ParseHeatmapError::Json(_))));
+    assert!(matches!(
+        HeatmapData::from_json(r#"{"2025-03-14": "x"}"#, &DateFormat::ISO),
+        Err(ParseHeatmapError::Value { line: 1, .. })
+    ));
+}
+
+#[test]
+fn heatmap_scale_and_layout() {
+    let scale = HeatmapScale::new([10.0, 1.0, 5.0]);
+    assert_eq!(scale.thresholds(), &[1.0, 5.0, 10.0]);
+    assert_eq!(scale.max_level(), 3);
+    assert_eq!(scale.level(None), 0);
+    assert_eq!(scale.level(Some(0.5)), 0);
+    assert_eq!(scale.level(Some(1.0)), 1);
+    assert_eq!(scale.level(Some(9.9)), 2);
+    assert_eq!(scale.level(Some(100.0)), 3);
+
+    // 2025-03-14 是周五，按周日起始时最后一列到周五为止
+    let today = ymd(2025, 3, 14);
+    let layout = HeatmapLayout::new(today, Weekday::Sun);
+    assert_eq!(layout.weeks.len(), HEATMAP_WEEKS);
+    let last = layout.weeks[HEATMAP_WEEKS - 1];
+    assert_eq!(last[0], Some(ymd(2025, 3, 9)));
+    assert_eq!(last[5], Some(today));
+    assert_eq!(last[6], None);
+    assert_eq!(layout.weeks[0][0], Some(ymd(2024, 3, 10)));
+    assert_eq!(layout.weeks[0][0].unwrap().weekday(), Weekday::Sun);
+
+    let monday_start = HeatmapLayout::new(today, Weekday::Mon);
+    assert_eq!(monday_start.weeks[HEATMAP_WEEKS - 1][0], Some(ymd(2025, 3, 10)));
+
+    let month_starts = layout.month_starts();
+    assert_eq!(month_starts.first(), Some(&(3, ymd(2024, 4, 1))));
+    assert_eq!(month_starts.last().unwrap().1, ymd(2025, 3, 1));
+    assert_eq!(month_starts.len(), 12);
+
+    assert_eq!(heatmap_tooltip(today, Some(3.0), Locale::Zh), "2025-03-14 周五：3");
+    assert_eq!(heatmap_tooltip(today, None, Locale::En), "Friday, 2025-03-14: no data");
+}
+
+#[test]
+fn zone_instants_and_conversion() {
+    let shanghai = Tz::Asia__Shanghai;
+    let new_york = Tz::America__New_York;
+
+    let start = start_of_day(ymd(2025, 3, 3), &shanghai);
+    assert_eq!(start.to_rfc3339(), "2025-03-03T00:00:00+08:00");
+    // 上海周一 0 点，纽约还是周日上午
+    assert_eq!(date_in_zone(&start, new_york), ymd(2025, 3, 2));
+    assert_eq!(
+        convert_start_of_day(ymd(2025, 3, 3), shanghai, new_york).to_rfc3339(),
+        "2025-03-02T11:00:00-05:00"
+    );
+
+    // 夏令时切换当天只有 23 小时
+    let (start, end) = range_instants(DateRange::single(ymd(2025, 3, 9)), &new_york);
+    assert_eq!((end - start).num_hours(), 23);
+    assert_eq!(zone_abbreviation(new_york, ymd(2025, 3, 8)), "EST");
+    assert_eq!(zone_abbreviation(new_york, ymd(2025, 3, 10)), "EDT");
+    assert_eq!(zone_abbreviation(shanghai, ymd(2025, 3, 10)), "CST");
+
+    // 圣地亚哥在 0 点跳到 1 点，当天最早的时刻是 1 点
+    let start = start_of_day(ymd(2024, 9, 8), &Tz::America__Santiago);
+    assert_eq!(start.to_rfc3339(), "2024-09-08T01:00:00-03:00");
+}
+
+#[test]
+fn calendar_value_instants_follow_clock_zone() {
+    let mut calendar = Calendar::with_clock(Clock::Zone(Tz::Asia__Shanghai));
+    calendar.selected_date = Some(ymd(2025, 3, 1));
+    let (start, end) = calendar.value_instants().unwrap();
+    assert_eq!(start.to_rfc3339(), "2025-03-01T00:00:00+08:00");
+    assert_eq!(end.to_rfc3339(), "2025-03-02T00:00:00+08:00");
+
+    calendar.clock = Clock::Fixed(ymd(2025, 3, 1));
+    let (start, _) = calendar.value_instants().unwrap();
+    assert_eq!(start.to_rfc3339(), "2025-03-01T00:00:00+00:00");
+}
+
+#[test]
+fn business_day_arithmetic() {
+    let mut calendar = calendar_at(ymd(2025, 3, 1));
+    calendar.working_days = WorkingDays::default().holidays([ymd(2025, 3, 10)]);
+
+    // 2025-03-07 是周五，跳过周末和 3 月 10 日的假期
+    assert_eq!(calendar.add_business_days(ymd(2025, 3, 7), 1), Some(ymd(2025, 3, 11)));
+    assert_eq!(calendar.add_business_days(ymd(2025, 3, 7), 0), Some(ymd(2025, 3, 7)));
+    assert_eq!(calendar.sub_business_days(ymd(2025, 3, 11), 1), Some(ymd(2025, 3, 7)));
+    assert_eq!(calendar.next_working_day(ymd(2025, 3, 8)), Some(ymd(2025, 3, 11)));
+    assert!(!calendar.is_working_day(&ymd(2025, 3, 9)));
+
+    assert_eq!(calendar.count_working_days(DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 31))), 20);
+    assert_eq!(calendar.count_working_days(DateRange::single(ymd(2025, 3, 8))), 0);
+
+    // 周五、周六为周末
+    calendar.working_days = WorkingDays::default().weekend([Weekday::Fri, Weekday::Sat]);
+    assert_eq!(calendar.next_working_day(ymd(2025, 3, 6)), Some(ymd(2025, 3, 9)));
+    assert_eq!(calendar.count_working_days(DateRange::new(ymd(2025, 3, 2), ymd(2025, 3, 8))), 5);
+
+    calendar.working_days = WorkingDays::default().weekend([
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]);
+    assert_eq!(calendar.next_working_day(ymd(2025, 3, 6)), None);
+}
+
+proptest! {
+    #[test]
+    fn working_day_count_matches_day_by_day(
+        start in date_in_range(),
+        len in 0i64..60,
+        weekend in prop::collection::vec(0u8..7, 0..4),
+    ) {
+        let weekend: Vec<_> = weekend
+            .into_iter()
+            .map(|idx| Weekday::try_from(idx).unwrap())
+            .collect();
+        let working_days = WorkingDays::default().weekend(weekend).holidays([start]);
+        let Some(end) = start.checked_add_signed(chrono::TimeDelta::days(len)) else {
+            return Ok(());
+        };
+        let range = DateRange::new(start, end);
+        // iter_days 不会产出 NaiveDate::MAX，按偏移逐天取日期
+        let expected = (0..range.num_days() as u64)
+            .filter_map(|offset| start.checked_add_days(chrono::Days::new(offset)))
+            .filter(|date| working_days.is_working_day(date))
+            .count() as i64;
+        prop_assert_eq!(working_days.count(range), expected);
+    }
+}
+
+#[test]
+fn relative_descriptions() {
+    // 2025-03-05 是周三
+    let today = ymd(2025, 3, 5);
+    let zh = |date| describe_relative(date, today, Locale::Zh, RelativeStyle::Relative);
+    let en = |date| describe_relative(date, today, Locale::En, RelativeStyle::Relative);
+
+    assert_eq!(zh(today), "今天");
+    assert_eq!(en(ymd(2025, 3, 6)), "tomorrow");
+    assert_eq!(zh(ymd(2025, 3, 8)), "3 天后");
+    assert_eq!(en(ymd(2025, 3, 19)), "in 2 weeks");
+    assert_eq!(zh(ymd(2025, 5, 5)), "2 个月后");
+    assert_eq!(en(ymd(2027, 3, 4)), "in 1 year");
+
+    assert_eq!(zh(ymd(2025, 3, 3)), "本周一");
+    assert_eq!(zh(ymd(2025, 2, 25)), "上周二");
+    assert_eq!(en(ymd(2025, 2, 25)), "last Tuesday");
+    assert_eq!(zh(ymd(2025, 2, 20)), "1 周前");
+    assert_eq!(en(ymd(2024, 12, 1)), "3 months ago");
+
+    let deadline = |date, locale| describe_relative(date, today, locale, RelativeStyle::Deadline);
+    assert_eq!(deadline(ymd(2025, 2, 28), Locale::Zh), "已过期 5 天");
+    assert_eq!(deadline(ymd(2025, 3, 4), Locale::En), "overdue by 1 day");
+    assert_eq!(deadline(today, Locale::Zh), "今天到期");
+    assert_eq!(deadline(ymd(2025, 3, 8), Locale::Zh), "3 天后");
+
+    let range = DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 10));
+    assert_eq!(describe_relative_range(range, today, Locale::Zh, RelativeStyle::Relative), "进行中");
+    let range = DateRange::new(ymd(2025, 3, 8), ymd(2025, 3, 10));
+    assert_eq!(describe_relative_range(range, today, Locale::En, RelativeStyle::Relative), "in 3 days");
+}
+
+#[test]
+fn fixed_clock_never_rolls_over() {
+    assert_eq!(Clock::Fixed(ymd(2025, 3, 5)).until_next_day(), None);
+    let delay = Clock::Zone(Tz::Asia__Tokyo).until_next_day().unwrap();
+    assert!(delay.as_secs() <= 24 * 60 * 60);
+}